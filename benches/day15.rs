@@ -0,0 +1,51 @@
+//! Compares the two A* implementations of day 15 on a generated 100x100 map
+//! tiled 5x5, like the real part 2. Run with `cargo bench --bench day15`.
+use aoc2021::bench::Stats;
+use aoc2021::day15::{dense_a_star, do_a_star, RiskMap, TiledMap};
+use aoc2021::grid::{Grid, Pos};
+use aoc2021::search::Found;
+use std::time::Instant;
+
+const RUNS: usize = 10;
+
+/// A fixed pseudo-random map with risks from 1 to 9.
+fn generate_map(size: usize) -> Grid<u32> {
+    let mut state = 0x2021_u64;
+    Grid::from_fn(size, size, |_| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as u32 % 9 + 1
+    })
+}
+
+fn measure(
+    name: &str,
+    map: &TiledMap,
+    search: impl Fn(Pos, Pos, &TiledMap) -> Option<Found<Pos, u32>>,
+) -> u32 {
+    let target = (map.width() - 1, map.height() - 1);
+    let mut samples = Vec::new();
+    let mut found = None;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        found = search((0, 0), target, map);
+        samples.push(start.elapsed());
+    }
+    let found = found.expect("target is reachable");
+    let stats = Stats::from_samples(samples);
+    println!(
+        "{:<10} cost {}  expanded {:>6}  min {:>10.2?}  median {:>10.2?}  max {:>10.2?}",
+        name, found.cost, found.expanded, stats.min, stats.median, stats.max
+    );
+    found.cost
+}
+
+fn main() {
+    let base = generate_map(100);
+    let map = TiledMap::new(&base, 5).expect("5 tiles fit");
+    println!("{}x{} map, {} runs", map.width(), map.height(), RUNS);
+    let sparse = measure("do_a_star", &map, |s, t, m| do_a_star(s, t, m));
+    let dense = measure("dense", &map, |s, t, m| dense_a_star(s, t, m));
+    assert_eq!(sparse, dense, "both searches must find the same risk");
+}
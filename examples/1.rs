@@ -1,25 +1,5 @@
-use aoc2021::{get_input, Error};
-use std::io::{BufRead, BufReader};
+use aoc2021::{day1::Day1, solution::print_day, Error};
 
 fn main() -> Result<(), Error> {
-    let input = BufReader::new(get_input(1)?);
-    let depth = input
-        .lines()
-        .map(|l| l.unwrap().parse::<i32>().unwrap())
-        .collect::<Vec<_>>();
-    let result1 = depth
-        .iter()
-        .zip(depth.iter().skip(1))
-        .filter(|(a, b)| b > a)
-        .count();
-    println!("first answer: {}", result1);
-    let w_iter = depth.windows(3).map(|w| w[0] + w[1] + w[2]);
-    let result2 = w_iter
-        .clone()
-        .zip(w_iter.skip(1))
-        .filter(|(a, b)| b > a)
-        .count();
-    println!("second answer: {}", result2);
-
-    Ok(())
+    print_day::<Day1>()
 }
@@ -1,138 +1,32 @@
-use aoc2021::{get_input, Error};
-use std::{
-    collections::HashMap,
-    io::{BufRead, BufReader},
-};
+//! Prints the answers. With `--path` the lowest-risk route is drawn over the
+//! risk map, `--tiled` uses the 5x5 map of part 2 and `--ppm <file>` writes
+//! the route as an image instead.
+use aoc2021::day15::{lowest_risk_path, path_to_ppm, render_path, Day15, TiledMap};
+use aoc2021::solution::{print_day_with_input, read_input, Solution};
+use aoc2021::Error;
 
-type Coord = (i32, i32);
-
-fn add_tile(pos: Coord, cost: &HashMap<Coord, u32>, whole_cost: &mut HashMap<Coord, u32>) {
-    let size = cost.keys().max_by_key(|(x, y)| x + y).unwrap().clone();
-    for (coord, c) in cost {
-        let cost_delta = (pos.0 + pos.1) as u32;
-        let new_cost_raw = c + cost_delta;
-        let new_cost = if new_cost_raw > 9 {
-            new_cost_raw - 9
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let ppm = args
+        .iter()
+        .position(|a| a == "--ppm")
+        .and_then(|i| args.get(i + 1));
+    let input = read_input(15)?;
+    if args.iter().any(|a| a == "--path") || ppm.is_some() {
+        let base = Day15::parse(&input)?;
+        let factor = if args.iter().any(|a| a == "--tiled") {
+            5
         } else {
-            new_cost_raw
+            1
         };
-        let new_coord = (
-            coord.0 + pos.0 * (size.0 + 1),
-            coord.1 + pos.1 * (size.0 + 1),
-        );
-        whole_cost.insert(new_coord, new_cost);
-    }
-}
-
-#[derive(Debug, PartialEq, Eq, Clone)]
-struct Node {
-    cost: u32,
-    pos: Coord,
-    dist: u32,
-    prev: Option<Coord>,
-}
-impl Node {
-    fn val(&self) -> u32 {
-        self.cost + 1 * self.dist
-    }
-}
-
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        other.val().partial_cmp(&self.val())
-    }
-}
-
-impl Ord for Node {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.val().cmp(&self.val())
-    }
-}
-fn do_a_star(start: Coord, target: Coord, cost: &HashMap<Coord, u32>) -> Node {
-    let mut paths = std::collections::BinaryHeap::new();
-    let mut nodes = HashMap::new();
-    paths.push(Node {
-        cost: 0,
-        pos: start,
-        dist: (target.0 - start.0 + target.1 - start.1) as u32,
-        prev: None,
-    });
-    nodes.insert(
-        start,
-        Node {
-            cost: 0,
-            pos: start,
-            dist: (target.0 - start.0 + target.1 - start.1) as u32,
-            prev: None,
-        },
-    );
-    loop {
-        let p = paths.pop().unwrap();
-        let cur_pos = p.pos;
-        if cur_pos == target {
-            return p;
-        }
-        let c = p.cost;
-        let x = cur_pos.0;
-        let y = cur_pos.1;
-        for pos in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
-            if let Some(cost) = cost.get(&pos) {
-                let dx = target.0 - pos.0;
-                let dy = target.1 - pos.1;
-                let node = Node {
-                    cost: c + cost,
-                    dist: (dx + dy) as u32,
-                    pos,
-                    prev: Some(cur_pos),
-                };
-                if let Some(old) = nodes.get(&pos) {
-                    if old.cost > c + cost {
-                        paths.push(node.clone());
-                        nodes.insert(pos, node);
-                    }
-                } else {
-                    paths.push(node.clone());
-                    nodes.insert(pos, node);
-                }
-            }
-        }
-    }
-}
-
-fn main() -> Result<(), Error> {
-    let input = BufReader::new(get_input(15)?);
-    let cost: HashMap<Coord, u32> = input
-        .lines()
-        .map(|l| l.unwrap())
-        .enumerate()
-        .flat_map(|(y, l)| {
-            l.clone()
-                .chars()
-                .enumerate()
-                .map(move |(x, c)| ((x as i32, y as i32), c as u32 - '0' as u32))
-                .collect::<Vec<_>>()
-        })
-        .collect();
-
-    let target = cost.keys().max_by_key(|(x, y)| x + y).unwrap().clone();
-
-    let answer1 = do_a_star((0, 0), target, &cost);
-    println!("Answer 1: {}", answer1.cost);
-
-    let mut whole_cost = HashMap::new();
-    for x in 0..5 {
-        for y in 0..5 {
-            add_tile((x, y), &cost, &mut whole_cost);
+        let cost = TiledMap::new(&base, factor)?;
+        let path = lowest_risk_path(&cost)
+            .map(|found| found.path)
+            .unwrap_or_default();
+        match ppm {
+            Some(file) => ex::fs::write(file, path_to_ppm(&cost, &path))?,
+            None => print!("{}", render_path(&cost, &path)),
         }
     }
-    let target = whole_cost
-        .keys()
-        .max_by_key(|(x, y)| x + y)
-        .unwrap()
-        .clone();
-
-    let answer2 = do_a_star((0, 0), target, &whole_cost);
-    println!("Answer 2: {}", answer2.cost);
-
-    Ok(())
+    print_day_with_input::<Day15>(&input)
 }
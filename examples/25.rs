@@ -0,0 +1,5 @@
+use aoc2021::{day25::Day25, solution::print_day, Error};
+
+fn main() -> Result<(), Error> {
+    print_day::<Day25>()
+}
@@ -0,0 +1,135 @@
+use crate::solution::Answer;
+use crate::Error;
+use std::collections::BTreeMap;
+
+/// Known-correct answers of each day, stored as TOML with one table per day:
+///
+/// ```toml
+/// [1]
+/// part1 = 1400
+/// part2 = 1429
+///
+/// [13]
+/// part1 = 17
+/// part2 = '''
+/// █████
+/// █   █
+/// █████'''
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    days: BTreeMap<u32, [Option<String>; 2]>,
+}
+
+/// Compares answers as text, ignoring trailing whitespace of each line and
+/// empty lines at the start and end.
+fn normalize(answer: &str) -> String {
+    answer
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
+
+fn to_toml(answer: &str) -> String {
+    if answer.contains('\n') && !answer.contains("'''") {
+        format!("'''\n{}'''", answer)
+    } else if let Ok(v) = answer.parse::<i64>() {
+        v.to_string()
+    } else {
+        toml::Value::String(answer.to_string()).to_string()
+    }
+}
+
+impl Answers {
+    pub fn load(path: &std::path::Path) -> Result<Self, Error> {
+        Self::parse(&ex::fs::read_to_string(path)?).map_err(|message| Error::InvalidFile {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.to_string())?;
+        let mut days = BTreeMap::new();
+        for (day, parts) in table {
+            let day: u32 = day.parse().map_err(|_| format!("'{}' is not a day", day))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("day {} is not a table", day))?;
+            let mut answers: [Option<String>; 2] = Default::default();
+            for (key, value) in parts {
+                let part = match key.as_str() {
+                    "part1" => 0,
+                    "part2" => 1,
+                    _ => return Err(format!("unknown key '{}' in day {}", key, day)),
+                };
+                answers[part] = Some(match value {
+                    toml::Value::String(s) => normalize(s),
+                    toml::Value::Integer(v) => v.to_string(),
+                    _ => return Err(format!("day {} {} is not a number or text", day, key)),
+                });
+            }
+            days.insert(day, answers);
+        }
+        Ok(Self { days })
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut res = String::new();
+        for (day, parts) in &self.days {
+            if !res.is_empty() {
+                res.push('\n');
+            }
+            res.push_str(&format!("[{}]\n", day));
+            for (i, answer) in parts.iter().enumerate() {
+                if let Some(answer) = answer {
+                    res.push_str(&format!("part{} = {}\n", i + 1, to_toml(answer)));
+                }
+            }
+        }
+        res
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> Result<(), Error> {
+        ex::fs::write(path, self.to_toml())?;
+        Ok(())
+    }
+
+    /// The recorded answer of `part` (1 or 2) of `day`.
+    pub fn get(&self, day: u32, part: usize) -> Option<&str> {
+        self.days.get(&day)?.get(part.checked_sub(1)?)?.as_deref()
+    }
+
+    pub fn set(&mut self, day: u32, part: usize, answer: &Answer) {
+        self.days.entry(day).or_default()[part - 1] = Some(normalize(&answer.to_string()));
+    }
+
+    /// Whether `answer` matches the recorded one. `None` if nothing is recorded.
+    pub fn check(&self, day: u32, part: usize, answer: &Answer) -> Option<bool> {
+        self.get(day, part)
+            .map(|expected| expected == normalize(&answer.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_numbers_and_text() {
+        let answers =
+            Answers::parse("[1]\npart1 = 7\npart2 = \"5\"\n\n[13]\npart2 = '''\n█ █  \n███'''\n")
+                .unwrap();
+        assert_eq!(answers.check(1, 1, &Answer::Num(7)), Some(true));
+        assert_eq!(answers.check(1, 2, &Answer::Num(6)), Some(false));
+        assert_eq!(answers.check(13, 1, &Answer::Num(17)), None);
+        assert_eq!(
+            answers.check(13, 2, &Answer::Text("█ █\n███\n".to_string())),
+            Some(true)
+        );
+        assert_eq!(Answers::parse(&answers.to_toml()), Ok(answers));
+    }
+}
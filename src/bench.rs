@@ -0,0 +1,188 @@
+use crate::solution::run_day;
+use crate::Error;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Names of the measured steps of a day.
+pub const STEPS: [&str; 3] = ["parse", "part1", "part2"];
+
+/// Differences below this are treated as noise and never reported as regressions.
+pub const NOISE: Duration = Duration::from_micros(100);
+
+/// Wall times of one step over all runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarizes the samples, of which there must be at least one.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Timings of parse, part 1 and part 2 of a day.
+#[derive(Debug, Clone)]
+pub struct DayBench {
+    pub day: u32,
+    pub steps: [Stats; 3],
+}
+
+/// Solves `day` on `input` `runs` times, at least once.
+pub fn bench_day(day: u32, input: &str, runs: usize) -> Result<DayBench, Error> {
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+    for _ in 0..runs.max(1) {
+        let result = run_day(day, input)?;
+        samples[0].push(result.parse);
+        let [(_, part1), (_, part2)] = result.answers()?;
+        samples[1].push(part1);
+        samples[2].push(part2);
+    }
+    Ok(DayBench {
+        day,
+        steps: samples.map(Stats::from_samples),
+    })
+}
+
+/// A step that got slower than the baseline allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    pub day: u32,
+    pub step: &'static str,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+/// Median times of earlier runs, stored as JSON like
+/// `{"days": {"1": {"parse": 1200, "part1": 300, "part2": 900}}}` in nanoseconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+    days: BTreeMap<u32, [Duration; 3]>,
+}
+
+impl Baseline {
+    pub fn load(path: &std::path::Path) -> Result<Self, Error> {
+        let text = ex::fs::read_to_string(path)?;
+        Self::from_json(&text).ok_or_else(|| Error::InvalidFile {
+            path: path.to_path_buf(),
+            message: "expected a benchmark baseline".to_string(),
+        })
+    }
+
+    pub fn from_json(text: &str) -> Option<Self> {
+        let value: Value = serde_json::from_str(text).ok()?;
+        let mut days = BTreeMap::new();
+        for (day, steps) in value.get("days")?.as_object()? {
+            let mut medians = [Duration::ZERO; 3];
+            for (median, step) in medians.iter_mut().zip(STEPS) {
+                *median = Duration::from_nanos(steps.get(step)?.as_u64()?);
+            }
+            days.insert(day.parse().ok()?, medians);
+        }
+        Some(Self { days })
+    }
+
+    pub fn to_json(&self) -> String {
+        let days: serde_json::Map<String, Value> = self
+            .days
+            .iter()
+            .map(|(day, medians)| {
+                let steps: serde_json::Map<String, Value> = STEPS
+                    .iter()
+                    .zip(medians)
+                    .map(|(step, m)| (step.to_string(), json!(m.as_nanos() as u64)))
+                    .collect();
+                (day.to_string(), Value::Object(steps))
+            })
+            .collect();
+        let mut text = serde_json::to_string_pretty(&json!({ "days": days }))
+            .expect("serializing a JSON value cannot fail");
+        text.push('\n');
+        text
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> Result<(), Error> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            ex::fs::create_dir_all(dir)?;
+        }
+        ex::fs::write(path, self.to_json())?;
+        Ok(())
+    }
+
+    /// Records the medians of `results`, keeping other days unchanged.
+    pub fn update(&mut self, results: &[DayBench]) {
+        for r in results {
+            self.days.insert(r.day, r.steps.map(|s| s.median));
+        }
+    }
+
+    /// Steps whose median is more than `threshold` (e.g. `0.2` for 20%) slower
+    /// than the baseline. Days without a baseline are skipped.
+    pub fn regressions(&self, results: &[DayBench], threshold: f64) -> Vec<Regression> {
+        let mut res = Vec::new();
+        for r in results {
+            let medians = match self.days.get(&r.day) {
+                Some(m) => m,
+                None => continue,
+            };
+            for ((step, baseline), stats) in STEPS.iter().zip(medians).zip(&r.steps) {
+                let current = stats.median;
+                if current > baseline.mul_f64(1.0 + threshold) && current - *baseline > NOISE {
+                    res.push(Regression {
+                        day: r.day,
+                        step,
+                        baseline: *baseline,
+                        current,
+                    });
+                }
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(median_us: u64) -> Stats {
+        let median = Duration::from_micros(median_us);
+        Stats {
+            min: median,
+            median,
+            max: median,
+        }
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let mut baseline = Baseline::default();
+        baseline.update(&[DayBench {
+            day: 3,
+            steps: [stats(1000), stats(1000), stats(10)],
+        }]);
+        let baseline = Baseline::from_json(&baseline.to_json()).unwrap();
+        let current = [DayBench {
+            day: 3,
+            steps: [stats(1100), stats(1500), stats(50)],
+        }];
+        assert_eq!(
+            baseline.regressions(&current, 0.2),
+            vec![Regression {
+                day: 3,
+                step: "part1",
+                baseline: Duration::from_micros(1000),
+                current: Duration::from_micros(1500),
+            }]
+        );
+    }
+}
@@ -0,0 +1,462 @@
+use aoc2021::answers::Answers;
+use aoc2021::bench::{bench_day, Baseline, DayBench, STEPS};
+use aoc2021::ledger::Ledger;
+use aoc2021::puzzle::get_puzzle;
+use aoc2021::solution::{read_input, result_to_json, run_day, DayResult, DAYS};
+use aoc2021::{Error, InputSource, DEFAULT_YEAR};
+use std::time::Duration;
+
+const USAGE: &str = "usage: aoc [run] [--json] [DAYS]...
+       aoc bench [--runs N] [--threshold PERCENT] [--baseline FILE] [--save] [DAYS]...
+       aoc verify [--answers FILE] [--record] [DAYS]...
+       aoc sample [DAYS]...
+
+DAYS is a day, a range like 3-7 or 3..7, or all (the default).
+bench only reads cached or local input, it never downloads.
+--json prints one JSON object per day instead of the table.
+verify compares the answers with answers.toml, --record writes them to it instead.
+sample runs the example of each puzzle description and compares the answers given there.";
+
+/// Parses a day selection like `7`, `3-7`, `3..7` or `all`.
+fn parse_days(arg: &str) -> Option<Vec<u32>> {
+    if arg == "all" {
+        return Some(DAYS.collect());
+    }
+    let (first, last) = match arg.split_once('-').or_else(|| arg.split_once("..")) {
+        Some((first, last)) => (first.parse().ok()?, last.parse().ok()?),
+        None => {
+            let day = arg.parse().ok()?;
+            (day, day)
+        }
+    };
+    if !DAYS.contains(&first) || !DAYS.contains(&last) || first > last {
+        return None;
+    }
+    Some((first..=last).collect())
+}
+
+fn format_duration(d: Duration) -> String {
+    let us = d.as_micros();
+    if us == 0 {
+        format!("{}ns", d.as_nanos())
+    } else if us < 1000 {
+        format!("{}µs", us)
+    } else if us < 1_000_000 {
+        format!("{:.1}ms", us as f64 / 1e3)
+    } else {
+        format!("{:.2}s", us as f64 / 1e6)
+    }
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    std::process::exit(2);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Run,
+    Bench,
+    Verify,
+    Sample,
+}
+
+struct Args {
+    mode: Mode,
+    days: Vec<u32>,
+    runs: usize,
+    threshold: f64,
+    baseline: std::path::PathBuf,
+    save: bool,
+    answers: std::path::PathBuf,
+    record: bool,
+    json: bool,
+}
+
+impl Args {
+    fn parse(args: impl Iterator<Item = String>) -> Self {
+        let mut res = Self {
+            mode: Mode::Run,
+            days: Vec::new(),
+            runs: 10,
+            threshold: 20.0,
+            baseline: "bench.json".into(),
+            save: false,
+            answers: "answers.toml".into(),
+            record: false,
+            json: false,
+        };
+        let mut args = args.peekable();
+        match args.peek().map(String::as_str) {
+            Some("run") => {
+                args.next();
+            }
+            Some("bench") => {
+                args.next();
+                res.mode = Mode::Bench;
+            }
+            Some("verify") => {
+                args.next();
+                res.mode = Mode::Verify;
+            }
+            Some("sample") => {
+                args.next();
+                res.mode = Mode::Sample;
+            }
+            _ => {}
+        }
+        while let Some(arg) = args.next() {
+            let only = |mode: Mode| {
+                if res.mode != mode {
+                    usage_error(&format!("{} only applies to {:?}", arg, mode).to_lowercase());
+                }
+            };
+            match arg.as_str() {
+                "--runs" | "--threshold" | "--baseline" | "--save" => only(Mode::Bench),
+                "--answers" | "--record" => only(Mode::Verify),
+                "--json" => only(Mode::Run),
+                _ => {}
+            }
+            let mut value = |name: &str| {
+                args.next()
+                    .unwrap_or_else(|| usage_error(&format!("{} needs a value", name)))
+            };
+            match arg.as_str() {
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    std::process::exit(0);
+                }
+                "--runs" => {
+                    res.runs = value("--runs")
+                        .parse()
+                        .ok()
+                        .filter(|r| *r > 0)
+                        .unwrap_or_else(|| usage_error("--runs must be a positive number"))
+                }
+                "--threshold" => {
+                    res.threshold = value("--threshold")
+                        .parse()
+                        .ok()
+                        .filter(|t: &f64| *t >= 0.0)
+                        .unwrap_or_else(|| usage_error("--threshold must be a percentage"))
+                }
+                "--baseline" => res.baseline = value("--baseline").into(),
+                "--save" => res.save = true,
+                "--answers" => res.answers = value("--answers").into(),
+                "--record" => res.record = true,
+                "--json" => res.json = true,
+                _ => match parse_days(&arg) {
+                    Some(d) => res.days.extend(d),
+                    None => usage_error(&format!("invalid argument '{}'", arg)),
+                },
+            }
+        }
+        if res.days.is_empty() {
+            res.days = DAYS.collect();
+        }
+        res
+    }
+}
+
+/// Records the answers in the ledger of the day. Only answers to the cached
+/// puzzle input are recorded, those for samples or other files mean nothing.
+fn record_answers(result: &DayResult) -> Result<(), Error> {
+    if !matches!(InputSource::from_env(), InputSource::Cache { .. }) {
+        return Ok(());
+    }
+    let mut ledger = Ledger::open(DEFAULT_YEAR, result.day)?;
+    for (part, (answer, _)) in (1..).zip(&result.parts) {
+        if let Ok(answer) = answer {
+            ledger.record_produced(part, &answer.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+fn solve(day: u32) -> Result<DayResult, Error> {
+    let result = run_day(day, &read_input(day)?)?;
+    if let Err(e) = record_answers(&result) {
+        eprintln!("cannot record the answers of day {}: {:?}", day, e);
+    }
+    Ok(result)
+}
+
+fn run_json(args: &Args) -> bool {
+    let mut ok = true;
+    for &day in &args.days {
+        let result = solve(day);
+        ok &= result.is_ok();
+        println!("{}", result_to_json(day, &result));
+    }
+    ok
+}
+
+fn run(args: &Args) -> bool {
+    if args.json {
+        return run_json(args);
+    }
+    let mut ok = true;
+    let mut long_answers = Vec::new();
+    println!(
+        "{:>3}  {:>16}  {:>16}  {:>10}",
+        "Day", "Part 1", "Part 2", "Time"
+    );
+    for &day in &args.days {
+        match solve(day) {
+            Ok(result) => {
+                let mut cells = Vec::new();
+                for (part, (answer, _)) in result.parts.iter().enumerate() {
+                    let answer = match answer {
+                        Ok(answer) => answer.to_string(),
+                        Err(e) => {
+                            ok = false;
+                            format!("error: {:?}", e)
+                        }
+                    };
+                    if answer.contains('\n') || answer.starts_with("error: ") {
+                        cells.push(format!("(part {} below)", part + 1));
+                        long_answers.push((day, part + 1, answer));
+                    } else {
+                        cells.push(answer);
+                    }
+                }
+                let total = result.parse + result.parts[0].1 + result.parts[1].1;
+                println!(
+                    "{:>3}  {:>16}  {:>16}  {:>10}",
+                    day,
+                    cells[0],
+                    cells[1],
+                    format_duration(total)
+                );
+            }
+            Err(e) => {
+                ok = false;
+                println!("{:>3}  error: {:?}", day, e);
+            }
+        }
+    }
+    for (day, part, answer) in long_answers {
+        println!("\nDay {} part {}:\n{}", day, part, answer);
+    }
+    ok
+}
+
+fn bench(args: &Args) -> bool {
+    let source = match InputSource::from_env() {
+        InputSource::Cache { .. } => InputSource::Cache { offline: true },
+        source => source,
+    };
+    let mut ok = true;
+    let mut results: Vec<DayBench> = Vec::new();
+    println!(
+        "{:>3}  {:>5}  {:>10}  {:>10}  {:>10}",
+        "Day", "Step", "Min", "Median", "Max"
+    );
+    for &day in &args.days {
+        let result = source
+            .read_to_string(DEFAULT_YEAR, day)
+            .and_then(|input| bench_day(day, &input, args.runs));
+        match result {
+            Ok(result) => {
+                for (step, stats) in STEPS.iter().zip(&result.steps) {
+                    println!(
+                        "{:>3}  {:>5}  {:>10}  {:>10}  {:>10}",
+                        day,
+                        step,
+                        format_duration(stats.min),
+                        format_duration(stats.median),
+                        format_duration(stats.max)
+                    );
+                }
+                results.push(result);
+            }
+            Err(e) => {
+                ok = false;
+                println!("{:>3}  error: {:?}", day, e);
+            }
+        }
+    }
+
+    let baseline = if args.baseline.exists() {
+        match Baseline::load(&args.baseline) {
+            Ok(b) => Some(b),
+            Err(e) => {
+                eprintln!("{:?}", e);
+                return false;
+            }
+        }
+    } else {
+        None
+    };
+    if let Some(baseline) = &baseline {
+        let regressions = baseline.regressions(&results, args.threshold / 100.0);
+        for r in &regressions {
+            println!(
+                "regression: day {} {} took {} (baseline {})",
+                r.day,
+                r.step,
+                format_duration(r.current),
+                format_duration(r.baseline)
+            );
+        }
+        if regressions.is_empty() {
+            println!(
+                "no regressions above {}% against {}",
+                args.threshold,
+                args.baseline.display()
+            );
+        } else {
+            ok = false;
+        }
+    }
+    if args.save {
+        let mut baseline = baseline.unwrap_or_default();
+        baseline.update(&results);
+        if let Err(e) = baseline.save(&args.baseline) {
+            eprintln!("{:?}", e);
+            return false;
+        }
+        println!("saved baseline to {}", args.baseline.display());
+    }
+    ok
+}
+
+fn print_mismatch(day: u32, part: usize, expected: &str, answer: &str) {
+    let sep = |s: &str| if s.contains('\n') { "\n" } else { " " };
+    println!(
+        "{:>3}  part {}  MISMATCH\n     expected:{}{}\n     got:{}{}",
+        day,
+        part,
+        sep(expected),
+        expected,
+        sep(answer),
+        answer
+    );
+}
+
+fn verify(args: &Args) -> bool {
+    // Only --record may start a new file, verifying against nothing must fail.
+    let mut answers = if args.record && !args.answers.exists() {
+        Answers::default()
+    } else if !args.answers.exists() {
+        eprintln!(
+            "{} does not exist, create it with aoc verify --record",
+            args.answers.display()
+        );
+        return false;
+    } else {
+        match Answers::load(&args.answers) {
+            Ok(a) => a,
+            Err(e) => {
+                eprintln!("{:?}", e);
+                return false;
+            }
+        }
+    };
+    let mut ok = true;
+    for &day in &args.days {
+        if !args.record && answers.get(day, 1).is_none() && answers.get(day, 2).is_none() {
+            println!("{:>3}  no answers recorded", day);
+            continue;
+        }
+        let result = match solve(day) {
+            Ok(result) => result,
+            Err(e) => {
+                ok = false;
+                println!("{:>3}  error: {:?}", day, e);
+                continue;
+            }
+        };
+        for (i, (answer, _)) in result.parts.iter().enumerate() {
+            let part = i + 1;
+            let answer = match answer {
+                Ok(answer) => answer,
+                Err(e) => {
+                    ok = false;
+                    println!("{:>3}  part {}  error: {:?}", day, part, e);
+                    continue;
+                }
+            };
+            if args.record {
+                answers.set(day, part, answer);
+                println!("{:>3}  part {}  recorded", day, part);
+                continue;
+            }
+            match answers.check(day, part, answer) {
+                Some(true) => println!("{:>3}  part {}  ok", day, part),
+                Some(false) => {
+                    ok = false;
+                    let expected = answers.get(day, part).unwrap_or_default();
+                    print_mismatch(day, part, expected, &answer.to_string());
+                }
+                None => println!("{:>3}  part {}  no answer recorded", day, part),
+            }
+        }
+    }
+    if args.record {
+        if let Err(e) = answers.save(&args.answers) {
+            eprintln!("{:?}", e);
+            return false;
+        }
+        println!("saved answers to {}", args.answers.display());
+    }
+    ok
+}
+
+/// Runs the example of each puzzle description. The answers are the last
+/// emphasized code of each part, which some descriptions use for other values.
+fn sample(args: &Args) -> bool {
+    let mut ok = true;
+    for &day in &args.days {
+        let sample = match get_puzzle(DEFAULT_YEAR, day) {
+            Ok(puzzle) => puzzle.sample(),
+            Err(e) => {
+                ok = false;
+                println!("{:>3}  error: {:?}", day, e);
+                continue;
+            }
+        };
+        let Some(sample) = sample else {
+            println!("{:>3}  no example found", day);
+            continue;
+        };
+        let result = match run_day(day, &sample.input) {
+            Ok(result) => result,
+            Err(e) => {
+                ok = false;
+                println!("{:>3}  error: {:?}", day, e);
+                continue;
+            }
+        };
+        for (i, ((answer, _), expected)) in result.parts.iter().zip(&sample.answers).enumerate() {
+            let answer = match answer {
+                Ok(answer) => answer.to_string(),
+                Err(e) => {
+                    ok = false;
+                    println!("{:>3}  part {}  error: {:?}", day, i + 1, e);
+                    continue;
+                }
+            };
+            if answer == *expected {
+                println!("{:>3}  part {}  ok", day, i + 1);
+            } else {
+                ok = false;
+                print_mismatch(day, i + 1, expected, &answer);
+            }
+        }
+    }
+    ok
+}
+
+fn main() {
+    let args = Args::parse(std::env::args().skip(1));
+    let ok = match args.mode {
+        Mode::Run => run(&args),
+        Mode::Bench => bench(&args),
+        Mode::Verify => verify(&args),
+        Mode::Sample => sample(&args),
+    };
+    if !ok {
+        std::process::exit(1);
+    }
+}
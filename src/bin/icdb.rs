@@ -0,0 +1,166 @@
+use aoc2021::intcode::asm::assemble;
+use aoc2021::intcode::debug::Debugger;
+use aoc2021::intcode::profile::Profile;
+use aoc2021::intcode::{Int, Machine};
+use aoc2021::solution::read_input;
+use aoc2021::Error;
+use std::io::IsTerminal;
+use std::io::Write;
+
+const USAGE: &str = "usage: icdb [--script FILE] [--input VALUES] PROGRAM
+       icdb [--trace FILE] [--profile] [--input VALUES] PROGRAM
+
+PROGRAM is a file with comma separated intcode or assembly if it ends in .asm,
+--day N uses the input of that day instead. Commands are read from stdin or the
+script, type help for a list.
+--input queues comma separated numbers as input for the program.
+--trace and --profile run the program without debugging, writing one line per
+executed instruction to FILE and/or printing the hottest addresses and loops.";
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    std::process::exit(2);
+}
+
+fn invalid(path: &str, message: impl std::fmt::Display) -> Error {
+    Error::InvalidFile {
+        path: path.into(),
+        message: message.to_string(),
+    }
+}
+
+fn parse_intcode(path: &str, text: &str) -> Result<Vec<Int>, Error> {
+    text.split(',')
+        .map(|v| {
+            v.trim()
+                .parse()
+                .map_err(|_| invalid(path, format!("not a number: {}", v.trim())))
+        })
+        .collect()
+}
+
+fn load(program: &str, day: bool) -> Result<Machine, Error> {
+    let mem = if day {
+        let day = program
+            .parse()
+            .unwrap_or_else(|_| usage_error(&format!("invalid day: {}", program)));
+        parse_intcode(&format!("input of day {}", day), &read_input(day)?)?
+    } else if program.ends_with(".asm") {
+        assemble(&ex::fs::read_to_string(program)?).map_err(|e| invalid(program, e))?
+    } else {
+        parse_intcode(program, &ex::fs::read_to_string(program)?)?
+    };
+    Ok(Machine::from_vec(mem))
+}
+
+/// Runs the program to the end, tracing and profiling it as requested.
+fn run(mut machine: Machine, trace: Option<&str>, profile: bool) -> Result<(), Error> {
+    let mut writer = match trace {
+        Some(path) => Some((path, std::io::BufWriter::new(ex::fs::File::create(path)?))),
+        None => None,
+    };
+    let mut write_error = None;
+    let mut prof = Profile::default();
+    let (halted, output) = machine.run_with_hook(None, |event| {
+        if let Some((path, w)) = writer.as_mut() {
+            if let Err(e) = writeln!(w, "{}", event) {
+                write_error.get_or_insert((path.to_string(), e));
+            }
+        }
+        if profile {
+            prof.record(event);
+        }
+    })?;
+    if let Some((path, w)) = writer.as_mut() {
+        if let Err(e) = w.flush() {
+            write_error.get_or_insert((path.to_string(), e));
+        }
+    }
+    if let Some((path, e)) = write_error {
+        return Err(ex::io::Error::Filesystem(path.into(), "writing", e).into());
+    }
+    let values: Vec<String> = output.iter().map(Int::to_string).collect();
+    println!("output {}", values.join(","));
+    if !halted {
+        println!("stopped waiting for input");
+    }
+    if profile {
+        print!("\n{}", prof.report(machine.memory(), 10));
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), Error> {
+    let mut script = None;
+    let mut program = None;
+    let mut day = false;
+    let mut input = Vec::new();
+    let mut trace = None;
+    let mut profile = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            "--script" => {
+                script = Some(
+                    args.next()
+                        .unwrap_or_else(|| usage_error("--script needs a file")),
+                )
+            }
+            "--input" => {
+                let values = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--input needs values"));
+                input = parse_intcode("--input", &values)
+                    .unwrap_or_else(|_| usage_error("--input takes comma separated numbers"));
+            }
+            "--trace" => {
+                trace = Some(
+                    args.next()
+                        .unwrap_or_else(|| usage_error("--trace needs a file")),
+                )
+            }
+            "--profile" => profile = true,
+            "--day" => {
+                day = true;
+                program = Some(
+                    args.next()
+                        .unwrap_or_else(|| usage_error("--day needs a day")),
+                );
+            }
+            a if a.starts_with('-') => usage_error(&format!("unknown option {}", a)),
+            _ if program.is_some() => usage_error("only one program can be debugged"),
+            _ => program = Some(arg),
+        }
+    }
+    let program = program.unwrap_or_else(|| usage_error("no program given"));
+    let mut machine = load(&program, day)?;
+    input.into_iter().for_each(|v| machine.push_input(v));
+    if trace.is_some() || profile {
+        if script.is_some() {
+            usage_error("--script cannot be combined with --trace or --profile");
+        }
+        return run(machine, trace.as_deref(), profile);
+    }
+    let mut debugger = Debugger::new(machine);
+    let mut out = std::io::stdout().lock();
+    let result = match script {
+        Some(script) => {
+            let file = ex::fs::File::open(&script)?;
+            debugger.repl(std::io::BufReader::new(file), &mut out, false)
+        }
+        None => {
+            let stdin = std::io::stdin();
+            let prompt = stdin.is_terminal();
+            debugger.repl(stdin.lock(), &mut out, prompt)
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("icdb: {}", e);
+        std::process::exit(1);
+    }
+    Ok(())
+}
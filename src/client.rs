@@ -0,0 +1,315 @@
+use crate::input::Error;
+use http_req::{request::Method, response::StatusCode, uri::Uri};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const BASE_URL: &str = "https://adventofcode.com";
+
+/// HTTP client shared by all requests to the Advent of Code server.
+///
+/// It identifies itself with a User-Agent, keeps a minimum interval between
+/// requests (persisted in `input/.last_request`, so it holds across processes)
+/// and retries server errors and timeouts of GET requests with exponential
+/// backoff.
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    user_agent: String,
+    min_interval: Duration,
+    retries: u32,
+    backoff: Duration,
+    state_path: Option<std::path::PathBuf>,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self {
+            base_url: BASE_URL.to_string(),
+            user_agent: format!(
+                "github.com/pathim/advent_of_code_2021 aoc2021/{}",
+                env!("CARGO_PKG_VERSION")
+            ),
+            min_interval: Duration::from_secs(3),
+            retries: 3,
+            backoff: Duration::from_secs(1),
+            state_path: Some(["input", ".last_request"].iter().collect()),
+        }
+    }
+}
+
+fn is_transient(e: &Error) -> bool {
+    use std::io::ErrorKind::*;
+    match e {
+        Error::Http(http_req::error::Error::IO(e)) => matches!(
+            e.kind(),
+            TimedOut | WouldBlock | ConnectionReset | ConnectionAborted | UnexpectedEof
+        ),
+        _ => false,
+    }
+}
+
+impl Client {
+    /// The default client, configured by the environment variables
+    /// `AOC_USER_AGENT` and `AOC_MIN_INTERVAL` (in seconds) if they are set.
+    pub fn from_env() -> Self {
+        let mut client = Self::default();
+        if let Ok(ua) = std::env::var("AOC_USER_AGENT") {
+            if !ua.trim().is_empty() {
+                client = client.with_user_agent(ua.trim());
+            }
+        }
+        if let Some(secs) = std::env::var("AOC_MIN_INTERVAL")
+            .ok()
+            .and_then(|s| s.trim().parse::<f64>().ok())
+            .filter(|s| s.is_finite() && *s >= 0.0)
+        {
+            client = client.with_min_interval(Duration::from_secs_f64(secs));
+        }
+        client
+    }
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+    pub fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+    /// Sets how often a failed request is retried and the delay before the first retry.
+    pub fn with_retries(mut self, retries: u32, backoff: Duration) -> Self {
+        self.retries = retries;
+        self.backoff = backoff;
+        self
+    }
+    /// Sets the file that stores the time of the last request, `None` keeps no state.
+    pub fn with_state_path(mut self, state_path: Option<std::path::PathBuf>) -> Self {
+        self.state_path = state_path;
+        self
+    }
+
+    /// Waits until `min_interval` has passed since the last recorded request and
+    /// records the current one.
+    ///
+    /// The state file is read and written without a lock, so processes starting
+    /// requests at the same moment can both see the same last request and send
+    /// within one interval of each other.
+    fn throttle(&self) -> Result<(), Error> {
+        let path = match &self.state_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let last = ex::fs::read_to_string(path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(|ms| UNIX_EPOCH + Duration::from_millis(ms));
+        if let Some(wait) = last.and_then(|l| {
+            (l + self.min_interval)
+                .duration_since(SystemTime::now())
+                .ok()
+        }) {
+            std::thread::sleep(wait.min(self.min_interval));
+        }
+        if let Some(dir) = path.parent() {
+            ex::fs::create_dir_all(dir)?;
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        ex::fs::write(path, now.to_string())?;
+        Ok(())
+    }
+
+    fn send_once(
+        &self,
+        uri: &Uri,
+        cookie: &str,
+        form: Option<&str>,
+    ) -> Result<(StatusCode, Vec<u8>), Error> {
+        self.throttle()?;
+        let mut body = Vec::new();
+        let mut request = http_req::request::Request::new(uri);
+        request
+            .header("Cookie", cookie)
+            .header("User-Agent", &self.user_agent);
+        if let Some(form) = form {
+            request
+                .method(Method::POST)
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .header("Content-Length", &form.len())
+                .body(form.as_bytes());
+        }
+        let resp = request.send(&mut body)?;
+        Ok((resp.status_code(), body))
+    }
+
+    /// Requests `<base_url>/<path>`, as a form POST if `form` is given, and returns
+    /// the status and body of the last attempt.
+    ///
+    /// Only GET requests are retried. A POST submits an answer, which the server
+    /// may have counted even if the response is an error or never arrives, so its
+    /// failures are returned immediately.
+    pub fn send(
+        &self,
+        path: &str,
+        cookie: &str,
+        form: Option<&str>,
+    ) -> Result<(StatusCode, Vec<u8>), Error> {
+        let url = format!("{}/{}", self.base_url, path);
+        let url_str: &str = &url;
+        let uri = Uri::try_from(url_str)?;
+        let mut delay = self.backoff;
+        let retries = if form.is_some() { 0 } else { self.retries };
+        for _ in 0..retries {
+            match self.send_once(&uri, cookie, form) {
+                Ok((status, _)) if status.is_server_err() => {}
+                Err(e) if is_transient(&e) => {}
+                res => return res,
+            }
+            std::thread::sleep(delay);
+            delay *= 2;
+        }
+        self.send_once(&uri, cookie, form)
+    }
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Puzzles unlock at midnight EST (UTC-5) on the day of December.
+pub fn unlock_time(year: u32, day: u32) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64);
+    UNIX_EPOCH + Duration::from_secs((days * 86400 + 5 * 3600) as u64)
+}
+
+#[cfg(test)]
+pub(crate) mod test_server {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Answers one request per entry of `responses` with the given status and body.
+    /// Returns the base URL and a handle yielding the raw requests received.
+    pub fn serve(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                loop {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some((head, rest)) = text.split_once("\r\n\r\n") {
+                        let len = head
+                            .lines()
+                            .find_map(|l| l.strip_prefix("Content-Length: "))
+                            .map(|l| l.trim().parse().unwrap())
+                            .unwrap_or(0);
+                        if rest.len() >= len {
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+                requests.push(String::from_utf8(request).unwrap());
+            }
+            requests
+        });
+        (base_url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_client(base_url: &str) -> Client {
+        Client::default()
+            .with_base_url(base_url)
+            .with_user_agent("aoc2021-test")
+            .with_retries(2, Duration::from_millis(1))
+            .with_state_path(None)
+    }
+
+    #[test]
+    fn retries_server_errors() {
+        let (base_url, handle) = test_server::serve(vec![(503, ""), (502, ""), (200, "ok")]);
+        let (status, body) = test_client(&base_url)
+            .send("2021/day/1/input", "session=abc", None)
+            .unwrap();
+        assert!(status.is_success());
+        assert_eq!(body, b"ok");
+        let requests = handle.join().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0].contains("User-Agent: aoc2021-test\r\n"));
+    }
+
+    #[test]
+    fn does_not_retry_posts() {
+        let (base_url, handle) = test_server::serve(vec![(503, "")]);
+        let (status, _) = test_client(&base_url)
+            .send("2021/day/1/answer", "session=abc", Some("level=1&answer=7"))
+            .unwrap();
+        assert!(status.is_server_err());
+        assert_eq!(handle.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn throttles_requests() {
+        let state = std::env::temp_dir().join(format!("aoc2021-throttle-{}", std::process::id()));
+        let client = Client::default()
+            .with_min_interval(Duration::from_millis(200))
+            .with_state_path(Some(state.clone()));
+        let start = std::time::Instant::now();
+        client.throttle().unwrap();
+        client.throttle().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(190));
+        std::fs::remove_file(state).unwrap();
+    }
+
+    #[test]
+    fn unlock_times() {
+        assert_eq!(
+            unlock_time(2021, 1)
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            1638334800
+        );
+        assert_eq!(
+            unlock_time(2020, 25)
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            1608872400
+        );
+    }
+}
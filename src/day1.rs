@@ -0,0 +1,55 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+
+pub struct Day1;
+
+fn count_increases(values: impl Iterator<Item = i32> + Clone) -> usize {
+    values
+        .clone()
+        .zip(values.skip(1))
+        .filter(|(a, b)| b > a)
+        .count()
+}
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    type Model = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        input
+            .lines()
+            .map(|l| l.trim().parse().map_err(|e| Error::parse(Self::DAY, e)))
+            .collect()
+    }
+
+    fn part1(depth: &Self::Model) -> Result<Answer, Error> {
+        Ok(count_increases(depth.iter().copied()).into())
+    }
+
+    fn part2(depth: &Self::Model) -> Result<Answer, Error> {
+        let w_iter = depth.windows(3).map(|w| w[0] + w[1] + w[2]);
+        Ok(count_increases(w_iter).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "199
+200
+208
+210
+200
+207
+240
+269
+260
+263";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day1>(SAMPLE, 7, 5);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+
+pub struct Day10;
+
+fn get_matching_closing(c: char) -> Option<char> {
+    Some(match c {
+        '(' => ')',
+        '<' => '>',
+        '[' => ']',
+        '{' => '}',
+        _ => return None,
+    })
+}
+fn get_score(c: char) -> Option<u64> {
+    Some(match c {
+        ')' => 3,
+        ']' => 57,
+        '}' => 1197,
+        '>' => 25137,
+        _ => return None,
+    })
+}
+fn get_score_completion(c: char) -> u64 {
+    match c {
+        ')' => 1,
+        ']' => 2,
+        '}' => 3,
+        '>' => 4,
+        _ => 0,
+    }
+}
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LineType {
+    Corrupted(u64),
+    Incomplete(u64),
+}
+
+fn validate_line(line: &str) -> Result<LineType, Error> {
+    let mut expected = Vec::new();
+    for c in line.chars() {
+        if let Some(close) = get_matching_closing(c) {
+            expected.push(close);
+        } else {
+            let score = get_score(c)
+                .ok_or_else(|| Error::parse(Day10::DAY, format!("not a delimiter: {}", c)))?;
+            if let Some(exp) = expected.pop() {
+                if exp != c {
+                    return Ok(LineType::Corrupted(score));
+                }
+            }
+        }
+    }
+    let res = expected
+        .iter()
+        .rev()
+        .copied()
+        .map(get_score_completion)
+        .fold(0, |accu, val| accu * 5 + val);
+    Ok(LineType::Incomplete(res))
+}
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Model = Vec<LineType>;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        input.lines().map(|l| validate_line(l.trim())).collect()
+    }
+
+    fn part1(lines: &Self::Model) -> Result<Answer, Error> {
+        Ok(lines
+            .iter()
+            .filter_map(|l| match l {
+                LineType::Corrupted(v) => Some(v),
+                LineType::Incomplete(_) => None,
+            })
+            .sum::<u64>()
+            .into())
+    }
+
+    fn part2(lines: &Self::Model) -> Result<Answer, Error> {
+        let mut incomplete: Vec<u64> = lines
+            .iter()
+            .filter_map(|l| match l {
+                LineType::Incomplete(v) => Some(*v),
+                LineType::Corrupted(_) => None,
+            })
+            .collect();
+        incomplete.sort();
+        incomplete
+            .get(incomplete.len() / 2)
+            .map(|v| Answer::from(*v))
+            .ok_or_else(|| Error::solve(Self::DAY, "no incomplete lines"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day10>(SAMPLE, 26397, 288957);
+    }
+
+    #[test]
+    fn classifies_lines() {
+        assert_eq!(
+            validate_line("{([(<{}[<>[]}>{[]{[(<()>").unwrap(),
+            LineType::Corrupted(1197)
+        );
+        assert_eq!(
+            validate_line("[({(<(())[]>[[{[]{<()<>>").unwrap(),
+            LineType::Incomplete(288957)
+        );
+        assert_eq!(validate_line("()").unwrap(), LineType::Incomplete(0));
+        assert!(validate_line("(x)").is_err());
+    }
+}
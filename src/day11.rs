@@ -0,0 +1,99 @@
+use crate::grid::{Grid, Pos};
+use crate::solution::{Answer, Solution};
+use crate::Error;
+
+pub struct Day11;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Octopus(u32);
+
+impl TryFrom<char> for Octopus {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        if value.is_ascii_digit() {
+            Ok(Self(value as u32 - '0' as u32))
+        } else {
+            Err(value)
+        }
+    }
+}
+impl Octopus {
+    fn inc(&mut self) -> bool {
+        self.0 += 1;
+        self.0 == 10
+    }
+    fn reset(&mut self) {
+        if self.0 >= 10 {
+            self.0 = 0;
+        }
+    }
+}
+
+type Area = Grid<Octopus>;
+
+fn increase(area: &mut Area, pos: Pos) -> u32 {
+    let mut flashes = 0;
+    if area[pos].inc() {
+        flashes += 1;
+        let neighbors: Vec<Pos> = area.neighbors8(pos).collect();
+        for n in neighbors {
+            flashes += increase(area, n);
+        }
+    }
+    flashes
+}
+
+fn step(area: &mut Area) -> u32 {
+    let positions: Vec<Pos> = area.positions().collect();
+    let flashes = positions.into_iter().map(|pos| increase(area, pos)).sum();
+    area.values_mut().for_each(Octopus::reset);
+    flashes
+}
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Model = Area;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        Grid::parse(input, |c| Octopus::try_from(c).ok()).map_err(|e| Error::parse(Self::DAY, e))
+    }
+
+    fn part1(area: &Self::Model) -> Result<Answer, Error> {
+        let mut area = area.clone();
+        let total_flashes: u32 = (0..100).map(|_| step(&mut area)).sum();
+        Ok(total_flashes.into())
+    }
+
+    fn part2(area: &Self::Model) -> Result<Answer, Error> {
+        let mut area = area.clone();
+        let size = area.values().count() as u32;
+        let mut n = 1u32;
+        while step(&mut area) != size {
+            n += 1;
+        }
+        Ok(n.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day11>(SAMPLE, 1656, 195);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::collections::{HashMap, HashSet};
+
+pub struct Day12;
+
+type Caves = HashMap<String, HashSet<String>>;
+
+fn is_small(cave: &str) -> bool {
+    cave.starts_with(|c: char| c.is_ascii_lowercase())
+}
+
+fn new_paths(from_to: &Caves, path: &[String]) -> Vec<Vec<String>> {
+    let mut res = Vec::new();
+    let next_caves = path.last().and_then(|last| from_to.get(last));
+    for next in next_caves.into_iter().flatten() {
+        if is_small(next) && path.contains(next) {
+            continue;
+        }
+        let mut new_path = path.to_vec();
+        new_path.push(next.clone());
+        res.push(new_path);
+    }
+    res
+}
+
+fn cave_still_possible(path: &[String], cave: &str) -> bool {
+    if cave == "end" {
+        return true;
+    }
+    if !is_small(cave) {
+        return true;
+    }
+    let mut unique = HashSet::new();
+    let mut already_in = false;
+    let mut already_double = false;
+    for c in path {
+        if !is_small(c) {
+            continue;
+        }
+        if c == cave {
+            if already_in {
+                return false;
+            }
+            already_in = true;
+        }
+        if !unique.insert(c) {
+            already_double = true;
+        }
+    }
+    if already_double {
+        !already_in
+    } else {
+        true
+    }
+}
+
+fn new_paths2(from_to: &Caves, path: &[String]) -> Vec<Vec<String>> {
+    let mut res = Vec::new();
+    let next_caves = path.last().and_then(|last| from_to.get(last));
+    for next in next_caves.into_iter().flatten() {
+        if cave_still_possible(path, next) {
+            let mut new_path = path.to_vec();
+            new_path.push(next.clone());
+            res.push(new_path);
+        }
+    }
+    res
+}
+
+fn count_paths(from_to: &Caves, extend: fn(&Caves, &[String]) -> Vec<Vec<String>>) -> usize {
+    let mut paths = vec![vec!["start".to_string()]];
+    let mut count = 0;
+    while !paths.is_empty() {
+        let (finished, unfinished) = paths
+            .iter()
+            .flat_map(|p| extend(from_to, p))
+            .partition::<Vec<_>, _>(|x| x.last().map(String::as_str) == Some("end"));
+        count += finished.len();
+        paths = unfinished;
+    }
+    count
+}
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    type Model = Caves;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        let mut from_to = HashMap::new();
+        for l in input.lines() {
+            let (c1, c2) = l
+                .trim()
+                .split_once('-')
+                .ok_or_else(|| Error::parse(Self::DAY, format!("not a connection: {}", l)))?;
+            for (a, b) in [(c1, c2), (c2, c1)] {
+                if b == "start" || a == "end" {
+                    continue;
+                }
+                from_to
+                    .entry(a.to_string())
+                    .or_insert_with(HashSet::new)
+                    .insert(b.to_string());
+            }
+        }
+        Ok(from_to)
+    }
+
+    fn part1(from_to: &Self::Model) -> Result<Answer, Error> {
+        Ok(count_paths(from_to, new_paths).into())
+    }
+
+    fn part2(from_to: &Self::Model) -> Result<Answer, Error> {
+        Ok(count_paths(from_to, new_paths2).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day12>(SAMPLE, 10, 36);
+    }
+
+    #[test]
+    fn solves_larger_sample() {
+        assert_answers::<Day12>(
+            "dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sj
+kj-HN
+kj-dc",
+            19,
+            103,
+        );
+    }
+}
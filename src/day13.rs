@@ -0,0 +1,126 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::collections::HashSet;
+
+pub struct Day13;
+
+type Dots = HashSet<(i32, i32)>;
+
+fn fold_x(dots: &Dots, pos: i32) -> Dots {
+    dots.iter()
+        .map(|&d| if d.0 < pos { d } else { (2 * pos - d.0, d.1) })
+        .collect()
+}
+fn fold_y(dots: &Dots, pos: i32) -> Dots {
+    dots.iter()
+        .map(|&d| if d.1 < pos { d } else { (d.0, 2 * pos - d.1) })
+        .collect()
+}
+
+fn fold(dots: &Dots, (is_x, pos): (bool, i32)) -> Dots {
+    if is_x {
+        fold_x(dots, pos)
+    } else {
+        fold_y(dots, pos)
+    }
+}
+
+fn draw(dots: &Dots) -> String {
+    let mut image = Vec::new();
+    for d in dots {
+        while image.len() <= d.1 as usize {
+            image.push(Vec::new());
+        }
+        let line = &mut image[d.1 as usize];
+        while line.len() <= d.0 as usize {
+            line.push(' ');
+        }
+        line[d.0 as usize] = '█';
+    }
+    image
+        .iter()
+        .map(|line| line.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_error(l: &str) -> Error {
+    Error::parse(Day13::DAY, format!("invalid line: {}", l))
+}
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    type Model = (Dots, Vec<(bool, i32)>);
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        let mut dots = HashSet::new();
+        let mut folds = Vec::new();
+        for l in input.lines().map(str::trim) {
+            if l.starts_with("fold") {
+                let (dir, pos) = l
+                    .rsplit_once(' ')
+                    .and_then(|(_, f)| f.split_once('='))
+                    .ok_or_else(|| parse_error(l))?;
+                folds.push((dir == "x", pos.parse().map_err(|_| parse_error(l))?));
+            } else if let Some((x, y)) = l.split_once(',') {
+                let x = x.parse().map_err(|_| parse_error(l))?;
+                let y = y.parse().map_err(|_| parse_error(l))?;
+                dots.insert((x, y));
+            }
+        }
+        if folds.is_empty() {
+            return Err(Error::parse(Self::DAY, "no folds"));
+        }
+        Ok((dots, folds))
+    }
+
+    fn part1((dots, folds): &Self::Model) -> Result<Answer, Error> {
+        Ok(fold(dots, folds[0]).len().into())
+    }
+
+    fn part2((dots, folds): &Self::Model) -> Result<Answer, Error> {
+        let dots = folds.iter().fold(dots.clone(), |d, f| fold(&d, *f));
+        Ok(Answer::Text(draw(&dots)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day13>(
+            SAMPLE,
+            17,
+            "█████
+█   █
+█   █
+█   █
+█████",
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::collections::HashMap;
+
+pub struct Day14;
+
+fn count<T>(counter: &mut HashMap<T, u64>, c: &T, v: u64)
+where
+    T: Eq + std::hash::Hash + Clone,
+{
+    if let Some(count) = counter.get_mut(c) {
+        *count += v;
+    } else {
+        counter.insert(c.clone(), v);
+    }
+}
+
+fn get_min_max_diff(dual_counter: &HashMap<String, u64>, last: char) -> u64 {
+    let mut counter = HashMap::new();
+    counter.insert(last, 1);
+    for (k, v) in dual_counter {
+        if let Some(c) = k.chars().next() {
+            count(&mut counter, &c, *v);
+        }
+    }
+
+    let max = counter.values().max().unwrap_or(&0);
+    let min = counter.values().min().unwrap_or(&0);
+
+    max - min
+}
+
+/// The polymer template and the two pairs each pair turns into.
+pub struct Polymer {
+    start: Vec<char>,
+    rules: HashMap<String, (String, String)>,
+}
+
+impl Polymer {
+    fn diff_after(&self, steps: usize) -> u64 {
+        let mut dual_counter = HashMap::new();
+        for s in self.start.windows(2) {
+            let s = s.iter().collect::<String>();
+            count(&mut dual_counter, &s, 1);
+        }
+        for _ in 0..steps {
+            let mut new_count = HashMap::<String, u64>::new();
+            for (k, v) in dual_counter.iter() {
+                match self.rules.get(k) {
+                    Some((r1, r2)) => {
+                        count(&mut new_count, r1, *v);
+                        count(&mut new_count, r2, *v);
+                    }
+                    None => count(&mut new_count, k, *v),
+                }
+            }
+            dual_counter = new_count;
+        }
+        get_min_max_diff(&dual_counter, *self.start.last().unwrap_or(&' '))
+    }
+}
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Model = Polymer;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        let mut lines = input.lines();
+        let start = lines
+            .next()
+            .ok_or_else(|| Error::parse(Self::DAY, "no template"))?
+            .trim()
+            .chars()
+            .collect::<Vec<_>>();
+        let mut rules = HashMap::new();
+        for l in lines.filter(|l| !l.trim().is_empty()) {
+            let invalid = || Error::parse(Self::DAY, format!("invalid rule: {}", l));
+            let (from, to) = l.split_once("->").ok_or_else(invalid)?;
+            let from = from.trim();
+            let mut chars = from.chars();
+            let to = to.trim().chars().next().ok_or_else(invalid)?;
+            let (a, b) = chars.next().zip(chars.next()).ok_or_else(invalid)?;
+            let n1: String = [a, to].iter().collect();
+            let n2: String = [to, b].iter().collect();
+            rules.insert(from.to_string(), (n1, n2));
+        }
+        Ok(Polymer { start, rules })
+    }
+
+    fn part1(polymer: &Self::Model) -> Result<Answer, Error> {
+        Ok(polymer.diff_after(10).into())
+    }
+
+    fn part2(polymer: &Self::Model) -> Result<Answer, Error> {
+        Ok(polymer.diff_after(40).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day14>(SAMPLE, 1588, 2188189693529u64);
+    }
+}
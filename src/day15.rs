@@ -0,0 +1,330 @@
+use crate::grid::{Grid, Pos, NEIGHBORS4};
+use crate::search::{self, Found};
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::collections::HashSet;
+
+pub struct Day15;
+
+type Coord = Pos;
+
+/// A map of risk levels, either stored or computed on demand.
+pub trait RiskMap {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+    /// The risk of entering `pos`, which must be inside the map. Risks are
+    /// between 1 and 9.
+    fn risk(&self, pos: Coord) -> u32;
+
+    /// The orthogonal neighbors of `pos` inside the map.
+    fn neighbors(&self, pos: Coord) -> impl Iterator<Item = Coord> {
+        let size = (self.width(), self.height());
+        NEIGHBORS4
+            .into_iter()
+            .filter_map(move |d| offset(pos, d, size))
+    }
+}
+
+/// `pos` moved by `(dx, dy)` if that stays inside a map of `(width, height)`.
+fn offset(
+    (x, y): Coord,
+    (dx, dy): (isize, isize),
+    (width, height): (usize, usize),
+) -> Option<Coord> {
+    let x = x.checked_add_signed(dx).filter(|x| *x < width)?;
+    let y = y.checked_add_signed(dy).filter(|y| *y < height)?;
+    Some((x, y))
+}
+
+impl RiskMap for Grid<u32> {
+    fn width(&self) -> usize {
+        Grid::width(self)
+    }
+    fn height(&self) -> usize {
+        Grid::height(self)
+    }
+    fn risk(&self, pos: Coord) -> u32 {
+        self[pos]
+    }
+}
+
+/// The full cave made of `factor` x `factor` tiles of a base map, with the
+/// risk increasing by one per tile to the right or down and wrapping from 9
+/// back to 1. Risks are computed from the base map when they are needed.
+#[derive(Debug, Clone, Copy)]
+pub struct TiledMap<'a> {
+    base: &'a Grid<u32>,
+    factor: usize,
+}
+
+impl<'a> TiledMap<'a> {
+    /// Fails if `factor` is 0, which would leave an empty cave, or so large
+    /// that the size of the cave does not fit in a `usize`.
+    pub fn new(base: &'a Grid<u32>, factor: usize) -> Result<Self, Error> {
+        if factor == 0 {
+            return Err(Error::solve(
+                Day15::DAY,
+                "a tiled map needs at least one tile",
+            ));
+        }
+        let width = base.width().checked_mul(factor);
+        let height = base.height().checked_mul(factor);
+        if width
+            .zip(height)
+            .and_then(|(w, h)| w.checked_mul(h))
+            .is_none()
+        {
+            return Err(Error::solve(
+                Day15::DAY,
+                format!("a tiling by {factor} is too large"),
+            ));
+        }
+        Ok(Self { base, factor })
+    }
+}
+
+impl RiskMap for TiledMap<'_> {
+    fn width(&self) -> usize {
+        self.base.width() * self.factor
+    }
+    fn height(&self) -> usize {
+        self.base.height() * self.factor
+    }
+    fn risk(&self, (x, y): Coord) -> u32 {
+        let (width, height) = (self.base.width(), self.base.height());
+        let tile = (x / width + y / height) as u32;
+        (self.base[(x % width, y % height)] + tile - 1) % 9 + 1
+    }
+}
+
+fn manhattan(a: Coord, b: Coord) -> u32 {
+    (a.0.abs_diff(b.0) + a.1.abs_diff(b.1)) as u32
+}
+
+/// A* over a hash map of visited nodes and a binary heap, see [`dense_a_star`]
+/// for the faster variant.
+pub fn do_a_star(start: Coord, target: Coord, cost: &impl RiskMap) -> Option<Found<Coord, u32>> {
+    search::a_star(
+        start,
+        |&pos| cost.neighbors(pos).map(|n| (n, cost.risk(n))),
+        |&pos| manhattan(pos, target),
+        |&pos| pos == target,
+    )
+}
+
+/// A* with flat arrays indexed by `y * width + x` and a bucket queue.
+///
+/// With risks between 1 and 9 and the Manhattan distance as heuristic, the
+/// estimate of a new node is at most 10 above the one being expanded and never
+/// below it, so a ring of 11 buckets indexed by estimate replaces the heap.
+///
+/// The arrays cover every cell of the map: the best risk found so far as a
+/// `u32` and the direction it was reached from as a byte. Searching a
+/// [`TiledMap`] therefore takes 5 bytes per cell, width x height x factor² x 5
+/// bytes in total, plus the queued nodes, even though its risks are not
+/// stored.
+pub fn dense_a_star(start: Coord, target: Coord, cost: &impl RiskMap) -> Option<Found<Coord, u32>> {
+    const BUCKETS: usize = 11;
+    const NONE: u8 = u8::MAX;
+    let size @ (width, height) = (cost.width(), cost.height());
+    let index = |(x, y): Coord| y * width + x;
+    let mut best = vec![u32::MAX; width * height];
+    // Index into `NEIGHBORS4` of the step that reached each cell.
+    let mut from = vec![NONE; best.len()];
+    let mut buckets: [Vec<Coord>; BUCKETS] = Default::default();
+    let mut estimate = manhattan(start, target);
+    let mut queued = 1;
+    let mut expanded = 0;
+    best[index(start)] = 0;
+    buckets[estimate as usize % BUCKETS].push(start);
+    while queued > 0 {
+        let Some(pos) = buckets[estimate as usize % BUCKETS].pop() else {
+            estimate += 1;
+            continue;
+        };
+        queued -= 1;
+        let c = best[index(pos)];
+        // Nodes are queued again when they improve, the outdated entries are
+        // the ones whose estimate does not match the bucket anymore.
+        if c + manhattan(pos, target) != estimate {
+            continue;
+        }
+        expanded += 1;
+        if pos == target {
+            let mut path = vec![pos];
+            let mut at = pos;
+            while from[index(at)] != NONE {
+                let (dx, dy) = NEIGHBORS4[from[index(at)] as usize];
+                at = offset(at, (-dx, -dy), size).expect("steps stay inside the map");
+                path.push(at);
+            }
+            path.reverse();
+            return Some(Found {
+                cost: c,
+                path,
+                expanded,
+            });
+        }
+        for (dir, d) in NEIGHBORS4.into_iter().enumerate() {
+            let Some(n) = offset(pos, d, size) else {
+                continue;
+            };
+            let next = c + cost.risk(n);
+            if next < best[index(n)] {
+                best[index(n)] = next;
+                from[index(n)] = dir as u8;
+                buckets[(next + manhattan(n, target)) as usize % BUCKETS].push(n);
+                queued += 1;
+            }
+        }
+    }
+    None
+}
+
+/// The lowest-risk path from the top left to the bottom right corner.
+pub fn lowest_risk_path(cost: &impl RiskMap) -> Option<Found<Coord, u32>> {
+    let target = (cost.width() - 1, cost.height() - 1);
+    dense_a_star((0, 0), target, cost)
+}
+
+fn lowest_risk(cost: &impl RiskMap) -> Result<Answer, Error> {
+    lowest_risk_path(cost)
+        .map(|found| found.cost.into())
+        .ok_or_else(|| Error::solve(Day15::DAY, "no path found"))
+}
+
+/// The risk map with the cells on `path` highlighted by ANSI escape codes.
+pub fn render_path(cost: &impl RiskMap, path: &[Coord]) -> String {
+    let on_path: HashSet<Coord> = path.iter().copied().collect();
+    let mut res = String::new();
+    for y in 0..cost.height() {
+        for x in 0..cost.width() {
+            let risk = cost.risk((x, y));
+            if on_path.contains(&(x, y)) {
+                res.push_str(&format!("\x1b[1;31m{}\x1b[0m", risk));
+            } else {
+                res.push_str(&risk.to_string());
+            }
+        }
+        res.push('\n');
+    }
+    res
+}
+
+/// The risk map as a binary PPM image with one pixel per cell. Higher risk is
+/// darker and the cells on `path` are red.
+pub fn path_to_ppm(cost: &impl RiskMap, path: &[Coord]) -> Vec<u8> {
+    let on_path: HashSet<Coord> = path.iter().copied().collect();
+    let mut res = format!("P6\n{} {}\n255\n", cost.width(), cost.height()).into_bytes();
+    for y in 0..cost.height() {
+        for x in 0..cost.width() {
+            if on_path.contains(&(x, y)) {
+                res.extend([255, 0, 0]);
+            } else {
+                let shade = 255 - (cost.risk((x, y)).min(9) * 25) as u8;
+                res.extend([shade; 3]);
+            }
+        }
+    }
+    res
+}
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    type Model = Grid<u32>;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        Grid::parse(input, |c| c.to_digit(10).filter(|risk| *risk > 0))
+            .map_err(|e| Error::parse(Self::DAY, e))
+    }
+
+    fn part1(cost: &Self::Model) -> Result<Answer, Error> {
+        lowest_risk(cost)
+    }
+
+    fn part2(cost: &Self::Model) -> Result<Answer, Error> {
+        lowest_risk(&TiledMap::new(cost, 5)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day15>(SAMPLE, 40, 315);
+    }
+
+    #[test]
+    fn renders_path() {
+        let cost = Day15::parse(SAMPLE).unwrap();
+        let found = lowest_risk_path(&cost).unwrap();
+        assert_eq!(found.path.first(), Some(&(0, 0)));
+        assert_eq!(found.path.last(), Some(&(9, 9)));
+        let risk: u32 = found.path[1..].iter().map(|p| cost[*p]).sum();
+        assert_eq!(risk, 40);
+
+        let text = render_path(&cost, &found.path);
+        assert!(text.starts_with("\x1b[1;31m1\x1b[0m163751742\n\x1b[1;31m1\x1b[0m381"));
+        assert_eq!(text.matches("\x1b[1;31m").count(), found.path.len());
+
+        let ppm = path_to_ppm(&TiledMap::new(&cost, 5).unwrap(), &[]);
+        assert!(ppm.starts_with(b"P6\n50 50\n255\n"));
+        assert_eq!(ppm.len(), "P6\n50 50\n255\n".len() + 50 * 50 * 3);
+    }
+
+    #[test]
+    fn tiles_lazily() {
+        let cost = Day15::parse(SAMPLE).unwrap();
+        let tiled = TiledMap::new(&cost, 5).unwrap();
+        assert_eq!((tiled.width(), tiled.height()), (50, 50));
+        let last_row: String = (0..50).map(|x| tiled.risk((x, 49)).to_string()).collect();
+        assert_eq!(
+            last_row,
+            "67554889357866599146897761125791887223681299833479"
+        );
+        let single = TiledMap::new(&cost, 1).unwrap();
+        assert!(cost.positions().all(|pos| single.risk(pos) == cost[pos]));
+        let large = TiledMap::new(&cost, 20).unwrap();
+        assert_eq!(large.risk((199, 199)), (1 + 38 - 1) % 9 + 1);
+        let found = lowest_risk_path(&large).unwrap();
+        assert_eq!(found.path.last(), Some(&(199, 199)));
+        let risk: u32 = found.path[1..].iter().map(|p| large.risk(*p)).sum();
+        assert_eq!(risk, found.cost);
+    }
+
+    #[test]
+    fn rejects_invalid_tiling() {
+        let cost = Day15::parse(SAMPLE).unwrap();
+        assert!(TiledMap::new(&cost, 0).is_err());
+        assert!(TiledMap::new(&cost, usize::MAX / 2).is_err());
+    }
+
+    #[test]
+    fn dense_search_matches_hash_map_search() {
+        let cost = Day15::parse(SAMPLE).unwrap();
+        for factor in [1, 3, 5] {
+            let tiled = TiledMap::new(&cost, factor).unwrap();
+            let target = (tiled.width() - 1, tiled.height() - 1);
+            let dense = dense_a_star((0, 0), target, &tiled).unwrap();
+            let sparse = do_a_star((0, 0), target, &tiled).unwrap();
+            assert_eq!(dense.cost, sparse.cost);
+            let risk: u32 = dense.path[1..].iter().map(|p| tiled.risk(*p)).sum();
+            assert_eq!(risk, dense.cost);
+        }
+        assert!(Day15::parse("10\n11").is_err());
+    }
+}
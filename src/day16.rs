@@ -0,0 +1,233 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+
+pub struct Day16;
+
+struct NibbleSource {
+    nibbles: Vec<u8>,
+    pos: usize,
+}
+
+fn hex_digit_to_value(d: char) -> Option<u8> {
+    d.to_digit(16).map(|v| v as u8)
+}
+
+trait BitSource {
+    fn next_bit(&mut self) -> Option<bool>;
+    fn get(&mut self, n: usize) -> Option<u64> {
+        let mut res = 0;
+        for _ in 0..n {
+            let bit = self.next_bit()?;
+            res = (res << 1) + bit as u64;
+        }
+        Some(res)
+    }
+}
+impl NibbleSource {
+    fn new(it: impl IntoIterator<Item = char>) -> Self {
+        let mut nibbles = it
+            .into_iter()
+            .map_while(hex_digit_to_value)
+            .collect::<Vec<_>>();
+        nibbles.reverse();
+        let pos = 4;
+        Self { nibbles, pos }
+    }
+}
+impl BitSource for NibbleSource {
+    fn next_bit(&mut self) -> Option<bool> {
+        if self.pos == 0 {
+            self.nibbles.pop();
+            self.pos = 4;
+        }
+        self.pos -= 1;
+        self.nibbles.last().map(|val| (val >> self.pos) & 1 == 1)
+    }
+}
+
+struct LimitedSource<'a> {
+    source: &'a mut dyn BitSource,
+    count: usize,
+}
+
+impl<'a> LimitedSource<'a> {
+    fn new(count: usize, source: &'a mut dyn BitSource) -> Self {
+        Self { source, count }
+    }
+}
+
+impl BitSource for LimitedSource<'_> {
+    fn next_bit(&mut self) -> Option<bool> {
+        if self.count == 0 {
+            None
+        } else {
+            self.count -= 1;
+            self.source.next_bit()
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Packet {
+    version: u64,
+    id: u64,
+    sub_packets: Vec<Packet>,
+    number: Option<u64>,
+}
+
+impl Packet {
+    fn new(data: &mut impl BitSource) -> Option<Self> {
+        let version = data.get(3)?;
+        let id = data.get(3)?;
+        let mut number = None;
+        let mut sub_packets = Vec::new();
+        match id {
+            4 => {
+                number = Some(get_immediate(data)?);
+            }
+            _ => sub_packets = get_sub_packets(data)?,
+        }
+        Some(Self {
+            version,
+            id,
+            number,
+            sub_packets,
+        })
+    }
+
+    fn version_sum(&self) -> u64 {
+        self.sub_packets
+            .iter()
+            .map(Packet::version_sum)
+            .sum::<u64>()
+            + self.version
+    }
+
+    /// The value of the expression, `None` if an operator has the wrong number of operands.
+    fn value(&self) -> Option<u64> {
+        let mut values = self.sub_packets.iter().map(Packet::value);
+        Some(match self.id {
+            0 => values.sum::<Option<u64>>()?,
+            1 => values.product::<Option<u64>>()?,
+            2 => values.collect::<Option<Vec<_>>>()?.into_iter().min()?,
+            3 => values.collect::<Option<Vec<_>>>()?.into_iter().max()?,
+            4 => self.number?,
+            id => {
+                let (a, b) = (values.next()??, values.next()??);
+                match id {
+                    5 => (a > b) as u64,
+                    6 => (a < b) as u64,
+                    _ => (a == b) as u64,
+                }
+            }
+        })
+    }
+}
+
+fn get_immediate(data: &mut impl BitSource) -> Option<u64> {
+    let mut res = 0;
+    loop {
+        let cont = data.next_bit()?;
+        let val = data.get(4)?;
+        res = (res << 4) + val;
+        if !cont {
+            return Some(res);
+        }
+    }
+}
+
+fn get_sub_packets(data: &mut impl BitSource) -> Option<Vec<Packet>> {
+    let length_type = data.next_bit()?;
+    let mut res = Vec::new();
+    if length_type {
+        let count = data.get(11)?;
+        for _ in 0..count {
+            res.push(Packet::new(data)?);
+        }
+    } else {
+        let length = data.get(15)? as usize;
+        let mut new_source = LimitedSource::new(length, data);
+        while let Some(p) = Packet::new(&mut new_source) {
+            res.push(p);
+        }
+    }
+    Some(res)
+}
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Model = Packet;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        let mut bits = NibbleSource::new(input.trim().chars());
+        Packet::new(&mut bits).ok_or_else(|| Error::parse(Self::DAY, "incomplete packet"))
+    }
+
+    fn part1(packet: &Self::Model) -> Result<Answer, Error> {
+        Ok(packet.version_sum().into())
+    }
+
+    fn part2(packet: &Self::Model) -> Result<Answer, Error> {
+        packet
+            .value()
+            .map(Answer::from)
+            .ok_or_else(|| Error::solve(Self::DAY, "operator with missing operands"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(hex: &str) -> Packet {
+        Day16::parse(hex).unwrap()
+    }
+
+    #[test]
+    fn reads_literal() {
+        let p = packet("D2FE28");
+        assert_eq!((p.version, p.id, p.number), (6, 4, Some(2021)));
+        assert!(p.sub_packets.is_empty());
+    }
+
+    #[test]
+    fn reads_operators() {
+        let p = packet("38006F45291200");
+        assert_eq!((p.version, p.id), (1, 6));
+        let numbers: Vec<_> = p.sub_packets.iter().map(|p| p.number).collect();
+        assert_eq!(numbers, [Some(10), Some(20)]);
+
+        let p = packet("EE00D40C823060");
+        assert_eq!((p.version, p.id), (7, 3));
+        let numbers: Vec<_> = p.sub_packets.iter().map(|p| p.number).collect();
+        assert_eq!(numbers, [Some(1), Some(2), Some(3)]);
+    }
+
+    #[test]
+    fn sums_versions() {
+        for (hex, sum) in [
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ] {
+            assert_eq!(packet(hex).version_sum(), sum, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn evaluates_expressions() {
+        for (hex, value) in [
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ] {
+            assert_eq!(packet(hex).value(), Some(value), "{}", hex);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::collections::{HashMap, HashSet};
+
+pub struct Day17;
+
+type Coord = (i32, i32);
+fn parse_arg(a: &str) -> Option<Coord> {
+    let (_, v) = a.trim().split_once('=')?;
+    let (min, max) = v.split_once("..")?;
+    Some((min.trim().parse().ok()?, max.trim().parse().ok()?))
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum Time {
+    Single(i32),
+    OpenEnd(i32),
+}
+
+impl Time {
+    fn contains(&self, other: &Self) -> bool {
+        let oi = match *other {
+            Time::Single(v) => v,
+            Time::OpenEnd(v) => v,
+        };
+        match *self {
+            Time::Single(v) => v == oi,
+            Time::OpenEnd(v) => v <= oi,
+        }
+    }
+}
+
+fn count_times(x: i32) -> HashMap<Time, i32> {
+    let mut res = HashMap::new();
+    let mut n = 1;
+    loop {
+        let num = 2 * x + n * n - n;
+        let den = 2 * n;
+        let v = num / den;
+        let r = num % den;
+        if r == 0 {
+            let t = if x > 0 && n >= v {
+                Time::OpenEnd(n)
+            } else {
+                Time::Single(n)
+            };
+            res.insert(t, v);
+        }
+        if if x > 0 { n >= v } else { v > -x } {
+            return res;
+        }
+        n += 1;
+    }
+}
+
+fn count_times_area(x: Coord, y: Coord) -> usize {
+    let mut res = HashSet::new();
+    let x_map: Vec<_> = (x.0..=x.1).map(count_times).collect();
+    let y_map: Vec<_> = (y.0..=y.1).map(count_times).collect();
+    for x_res in &x_map {
+        for y_res in &y_map {
+            for (y_time, y_vel) in y_res {
+                for (x_time, x_vel) in x_res {
+                    if x_time.contains(y_time) {
+                        let vel = (x_vel, y_vel);
+                        res.insert(vel);
+                    }
+                }
+            }
+        }
+    }
+    res.len()
+}
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    /// The x and y ranges of the target area.
+    type Model = (Coord, Coord);
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        let area = input
+            .trim()
+            .split_once(':')
+            .and_then(|(_, args)| args.split_once(','))
+            .and_then(|(x_arg, y_arg)| Some((parse_arg(x_arg)?, parse_arg(y_arg)?)));
+        area.ok_or_else(|| Error::parse(Self::DAY, "invalid target area"))
+    }
+
+    fn part1((_, y): &Self::Model) -> Result<Answer, Error> {
+        /*
+         * x(n)=(vx0+1)*vx0/2-((vx0-n)+1)*max(vx0-n,0)/2
+         */
+        let vy0 = -y.0 - 1;
+        Ok(((vy0 + 1) * vy0 / 2).into())
+    }
+
+    fn part2((x, y): &Self::Model) -> Result<Answer, Error> {
+        Ok(count_times_area(*x, *y).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "target area: x=20..30, y=-10..-5";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day17>(SAMPLE, 45, 112);
+    }
+}
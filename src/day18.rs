@@ -0,0 +1,270 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::{fmt::Display, num::ParseIntError, str::FromStr};
+
+pub struct Day18;
+
+#[derive(Debug, Clone)]
+pub enum Num {
+    Basic(i64),
+    Pair(Box<Num>, Box<Num>),
+}
+
+#[derive(Debug)]
+pub enum NumParseError {
+    ImbalancedBrackets,
+    NumError(ParseIntError),
+}
+
+impl From<ParseIntError> for NumParseError {
+    fn from(e: ParseIntError) -> Self {
+        Self::NumError(e)
+    }
+}
+
+impl Display for NumParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ImbalancedBrackets => write!(f, "imbalanced brackets"),
+            Self::NumError(e) => write!(f, "invalid number: {}", e),
+        }
+    }
+}
+
+impl FromStr for Num {
+    type Err = NumParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with('[') {
+            if !s.ends_with(']') {
+                return Err(NumParseError::ImbalancedBrackets);
+            }
+            // Find correct comma
+            let inner = &s[1..s.len() - 1];
+            let mut comma_pos = 0;
+            let mut level = 0;
+            for (idx, c) in inner.chars().enumerate() {
+                if c == '[' {
+                    level += 1;
+                }
+                if c == ']' {
+                    level -= 1;
+                }
+                if c == ',' && level == 0 {
+                    comma_pos = idx;
+                    break;
+                }
+            }
+            let first = &inner[..comma_pos];
+            let second = &inner[comma_pos + 1..];
+            let first_num: Num = first.parse()?;
+            let second_num: Num = second.parse()?;
+            Ok(Self::Pair(Box::new(first_num), Box::new(second_num)))
+        } else {
+            let value: i64 = s.parse()?;
+            Ok(Self::Basic(value))
+        }
+    }
+}
+impl std::ops::Add for Num {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut res = Num::Pair(Box::new(self), Box::new(rhs));
+        res.reduce();
+        res
+    }
+}
+impl Display for Num {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Basic(v) => write!(f, "{}", v),
+            Self::Pair(v1, v2) => write!(f, "[{},{}]", *v1, *v2),
+        }
+    }
+}
+impl Num {
+    fn reduce(&mut self) {
+        let mut cont = true;
+        while cont {
+            if self.explode(0).2 {
+                cont = true;
+            } else {
+                cont = self.split();
+            }
+        }
+    }
+    fn split(&mut self) -> bool {
+        match self {
+            Self::Basic(v) => {
+                if *v >= 10 {
+                    *self = Self::Pair(
+                        Box::new(Self::Basic(*v / 2)),
+                        Box::new(Self::Basic((*v + 1) / 2)),
+                    );
+                    true
+                } else {
+                    false
+                }
+            }
+            Self::Pair(v1, v2) => {
+                if !v1.split() {
+                    v2.split()
+                } else {
+                    true
+                }
+            }
+        }
+    }
+
+    fn explode(&mut self, level: u32) -> (Option<i64>, Option<i64>, bool) {
+        let mut res = (None, None, false);
+        match self {
+            Self::Basic(_) => {}
+            Self::Pair(v1, v2) => {
+                if level == 4 {
+                    res = (Some(v1.get_basic_value()), Some(v2.get_basic_value()), true);
+                    *self = Self::Basic(0);
+                    return res;
+                }
+                let (r1, r2, has_exp) = v1.explode(level + 1);
+                res.2 = has_exp;
+                if r1.is_some() || r2.is_some() {
+                    if let Some(val) = r2 {
+                        v2.add_exploded_value_left(val);
+                    }
+                    res.0 = r1;
+                    res.2 = true;
+                } else if !has_exp {
+                    let (r1, r2, has_exp) = v2.explode(level + 1);
+                    if let Some(val) = r1 {
+                        v1.add_exploded_value_right(val);
+                    }
+                    res.1 = r2;
+                    res.2 = has_exp;
+                }
+            }
+        }
+        res
+    }
+    fn get_basic_value(&self) -> i64 {
+        match self {
+            Self::Basic(v) => *v,
+            _ => panic!("Not a Basic value: {:?}", self),
+        }
+    }
+    fn add_exploded_value_right(&mut self, val: i64) {
+        match self {
+            Num::Basic(v) => *v += val,
+            Num::Pair(_, v2) => v2.add_exploded_value_right(val),
+        };
+    }
+    fn add_exploded_value_left(&mut self, val: i64) {
+        match self {
+            Num::Basic(v) => *v += val,
+            Num::Pair(v1, _) => v1.add_exploded_value_left(val),
+        };
+    }
+
+    fn magnitude(&self) -> i64 {
+        match self {
+            Self::Basic(v) => *v,
+            Self::Pair(v1, v2) => 3 * v1.magnitude() + 2 * v2.magnitude(),
+        }
+    }
+}
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    type Model = Vec<Num>;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        input
+            .lines()
+            .map(|l| l.trim().parse().map_err(|e| Error::parse(Self::DAY, e)))
+            .collect()
+    }
+
+    fn part1(numbers: &Self::Model) -> Result<Answer, Error> {
+        numbers
+            .iter()
+            .cloned()
+            .reduce(|a, b| a + b)
+            .map(|res| res.magnitude().into())
+            .ok_or_else(|| Error::solve(Self::DAY, "no numbers"))
+    }
+
+    fn part2(numbers: &Self::Model) -> Result<Answer, Error> {
+        let mut res2 = 0;
+        for (i1, v1) in numbers.iter().enumerate() {
+            for (i2, v2) in numbers.iter().cloned().enumerate() {
+                if i1 == i2 {
+                    continue;
+                }
+                let mag = (v1.clone() + v2).magnitude();
+                res2 = res2.max(mag);
+            }
+        }
+        Ok(res2.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day18>(SAMPLE, 4140, 3993);
+    }
+
+    fn explode_once(n: &str) -> String {
+        let mut n: Num = n.parse().unwrap();
+        assert!(n.explode(0).2);
+        n.to_string()
+    }
+
+    #[test]
+    fn explodes_pairs() {
+        assert_eq!(explode_once("[[[[[9,8],1],2],3],4]"), "[[[[0,9],2],3],4]");
+        assert_eq!(explode_once("[7,[6,[5,[4,[3,2]]]]]"), "[7,[6,[5,[7,0]]]]");
+        assert_eq!(explode_once("[[6,[5,[4,[3,2]]]],1]"), "[[6,[5,[7,0]]],3]");
+        assert_eq!(
+            explode_once("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]"),
+            "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"
+        );
+    }
+
+    #[test]
+    fn reduces_sums() {
+        let sum = |a: &str, b: &str| (a.parse::<Num>().unwrap() + b.parse().unwrap()).to_string();
+        assert_eq!(
+            sum("[[[[4,3],4],4],[7,[[8,4],9]]]", "[1,1]"),
+            "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
+        );
+        let mut n: Num = "[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]".parse().unwrap();
+        n.reduce();
+        assert_eq!(n.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+    }
+
+    #[test]
+    fn computes_magnitude() {
+        let magnitude = |n: &str| n.parse::<Num>().unwrap().magnitude();
+        assert_eq!(magnitude("[[1,2],[[3,4],5]]"), 143);
+        assert_eq!(
+            magnitude("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]"),
+            3488
+        );
+    }
+}
@@ -0,0 +1,459 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+pub struct Day19;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Coord([i64; 3]);
+impl FromStr for Coord {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(',').map(|p| p.trim().parse::<i64>().ok());
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Some(x)), Some(Some(y)), Some(Some(z)), None) => Ok(Self([x, y, z])),
+            _ => Err(Error::parse(
+                Day19::DAY,
+                format!("invalid coordinate: {}", s),
+            )),
+        }
+    }
+}
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Coord([
+            self.0[0] - rhs.0[0],
+            self.0[1] - rhs.0[1],
+            self.0[2] - rhs.0[2],
+        ])
+    }
+}
+impl Sub for &Coord {
+    type Output = Coord;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        *self - *rhs
+    }
+}
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Coord([
+            self.0[0] + rhs.0[0],
+            self.0[1] + rhs.0[1],
+            self.0[2] + rhs.0[2],
+        ])
+    }
+}
+impl Add for &Coord {
+    type Output = Coord;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        *self + *rhs
+    }
+}
+impl Coord {
+    fn normalize(&self) -> Self {
+        let mut c = [self.0[0].abs(), self.0[1].abs(), self.0[2].abs()];
+        c.sort();
+        Self([c[0], c[1], c[2]])
+    }
+    /// Whether the absolute components are nonzero and pairwise different, so
+    /// that the rotation between two views of this vector is unique.
+    fn has_distinct_axes(&self) -> bool {
+        let n = self.normalize().0;
+        n[0] != 0 && n[0] != n[1] && n[1] != n[2]
+    }
+    fn transform(&self, transform: &Self) -> Self {
+        Self(
+            transform
+                .0
+                .map(|t| t.signum() * self.0[t.unsigned_abs() as usize - 1]),
+        )
+    }
+    fn calc_transform(&self, to: &Self) -> Self {
+        let mut res = [0; 3];
+        for (r, t) in res.iter_mut().zip(&to.0) {
+            for (j, s) in self.0.iter().enumerate() {
+                if s.abs() == t.abs() {
+                    *r = (s.signum() * t.signum()) * (j as i64 + 1);
+                }
+            }
+        }
+        Self(res)
+    }
+}
+#[derive(Debug)]
+pub struct Sensor {
+    position: Cell<Option<Coord>>,
+    transformation: Cell<Coord>,
+    beacons: Vec<Coord>,
+    diffs_inv: HashMap<Coord, (usize, usize)>,
+}
+
+impl Sensor {
+    fn new<'a>(iter: &mut impl Iterator<Item = &'a str>) -> Result<Self, Error> {
+        let mut beacons = Vec::new();
+        for l in iter.by_ref() {
+            if l.is_empty() {
+                break;
+            }
+            beacons.push(l.parse()?);
+        }
+        let diffs_inv = HashMap::new();
+
+        let position = Cell::new(None);
+        let transformation = Cell::new(Coord([1, 2, 3]));
+        let mut res = Self {
+            position,
+            transformation,
+            beacons,
+            diffs_inv,
+        };
+        res.fill_diff_inv();
+        Ok(res)
+    }
+    fn fill_diff_inv(&mut self) {
+        for (i, c1) in self.beacons.iter().enumerate() {
+            for (j, c2) in self.beacons.iter().enumerate() {
+                if i != j {
+                    let diff: Coord = c1 - c2;
+                    let norm = diff.normalize();
+                    self.diffs_inv.insert(norm, (i, j));
+                }
+            }
+        }
+    }
+
+    fn count_same(&self, other: &Self) -> usize {
+        let k = self.diffs_inv.keys().copied().collect::<HashSet<_>>();
+        let o = other.diffs_inv.keys().copied().collect::<HashSet<_>>();
+        k.intersection(&o).count()
+    }
+
+    fn find_overlapping<'a>(&self, other: &'a [Self]) -> Option<&'a Self> {
+        for o in other {
+            if o.position.get().is_some() {
+                continue;
+            }
+            if self.count_same(o) >= 66 {
+                return Some(o);
+            }
+        }
+        None
+    }
+
+    fn find_position(&self, reference: &Self) -> Option<()> {
+        let mut coord_index_candidates: HashMap<usize, HashSet<usize>> = HashMap::new();
+        for (k, v) in self.diffs_inv.iter() {
+            if let Some(v2) = reference.diffs_inv.get(k) {
+                if let Some(cic) = coord_index_candidates.get_mut(&v.0) {
+                    cic.retain(|&c| c == v2.0 || c == v2.1);
+                } else {
+                    coord_index_candidates.insert(v.0, HashSet::from([v2.0, v2.1]));
+                }
+                if let Some(cic) = coord_index_candidates.get_mut(&v.1) {
+                    cic.retain(|&c| c == v2.0 || c == v2.1);
+                } else {
+                    coord_index_candidates.insert(v.1, HashSet::from([v2.0, v2.1]));
+                }
+            }
+        }
+        // Beacons whose counterpart in the reference is known, in a fixed order.
+        let mut matches: Vec<(usize, usize)> = coord_index_candidates
+            .into_iter()
+            .filter(|(_, c)| c.len() == 1)
+            .filter_map(|(i, c)| Some((i, c.into_iter().next()?)))
+            .collect();
+        matches.sort_unstable();
+
+        let tr = reference.transformation.get();
+        let position = reference.position.get()?;
+        let known: HashSet<Coord> = reference.absolute_beacons().collect();
+        for (k, &(s1, o1)) in matches.iter().enumerate() {
+            for &(s2, o2) in &matches[k + 1..] {
+                let diff = self.beacons[s1] - self.beacons[s2];
+                if !diff.has_distinct_axes() {
+                    continue;
+                }
+                let t = diff.calc_transform(
+                    &(reference.beacons[o1] - reference.beacons[o2]).transform(&tr),
+                );
+                if t.0.contains(&0) {
+                    continue;
+                }
+                let p = position + reference.beacons[o1].transform(&tr)
+                    - self.beacons[s1].transform(&t);
+                let overlap = self
+                    .beacons
+                    .iter()
+                    .filter(|c| known.contains(&(c.transform(&t) + p)))
+                    .count();
+                if overlap >= 12 {
+                    self.position.set(Some(p));
+                    self.transformation.set(t);
+                    return Some(());
+                }
+            }
+        }
+        None
+    }
+
+    fn absolute_beacons(&self) -> impl Iterator<Item = Coord> + '_ {
+        let t = self.transformation.get();
+        let p = self.position.get().unwrap_or(Coord([0, 0, 0]));
+        self.beacons.iter().map(move |c| c.transform(&t) + p)
+    }
+}
+
+/// All beacons and the positions of all scanners, relative to the first scanner.
+pub struct Scan {
+    beacons: HashSet<Coord>,
+    positions: Vec<Coord>,
+}
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+    type Model = Scan;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        let mut lines = input.lines().map(str::trim);
+        let mut sensors = Vec::new();
+        while let Some(l) = lines.next() {
+            if !l.starts_with("---") {
+                return Err(Error::parse(Self::DAY, format!("unexpected line: '{}'", l)));
+            }
+            sensors.push(Sensor::new(&mut lines)?);
+        }
+        let first = sensors
+            .first()
+            .ok_or_else(|| Error::parse(Self::DAY, "no scanners"))?;
+        first.position.set(Some(Coord([0, 0, 0])));
+
+        while sensors.iter().any(|s| s.position.get().is_none()) {
+            let mut found = false;
+            for s1 in sensors.iter().filter(|s| s.position.get().is_some()) {
+                while let Some(s2) = s1.find_overlapping(&sensors) {
+                    s2.find_position(s1)
+                        .ok_or_else(|| Error::solve(Self::DAY, "cannot align scanners"))?;
+                    found = true;
+                }
+            }
+            if !found {
+                return Err(Error::solve(Self::DAY, "some scanners do not overlap"));
+            }
+        }
+        Ok(Scan {
+            beacons: sensors.iter().flat_map(Sensor::absolute_beacons).collect(),
+            positions: sensors.iter().filter_map(|s| s.position.get()).collect(),
+        })
+    }
+
+    fn part1(scan: &Self::Model) -> Result<Answer, Error> {
+        Ok(scan.beacons.len().into())
+    }
+
+    fn part2(scan: &Self::Model) -> Result<Answer, Error> {
+        let max_dist = scan
+            .positions
+            .iter()
+            .flat_map(|x| scan.positions.iter().map(move |y| *y - *x))
+            .map(|x| x.0.iter().map(|a| a.abs()).sum::<i64>())
+            .max()
+            .unwrap_or(0);
+        Ok(max_dist.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "--- scanner 0 ---
+404,-588,-901
+528,-643,409
+-838,591,734
+390,-675,-793
+-537,-823,-458
+-485,-357,347
+-345,-311,381
+-661,-816,-575
+-876,649,763
+-618,-824,-621
+553,345,-567
+474,580,667
+-447,-329,318
+-584,868,-557
+544,-627,-890
+564,392,-477
+455,729,728
+-892,524,684
+-689,845,-530
+423,-701,434
+7,-33,-71
+630,319,-379
+443,580,662
+-789,900,-551
+459,-707,401
+
+--- scanner 1 ---
+686,422,578
+605,423,415
+515,917,-361
+-336,658,858
+95,138,22
+-476,619,847
+-340,-569,-846
+567,-361,727
+-460,603,-452
+669,-402,600
+729,430,532
+-500,-761,534
+-322,571,750
+-466,-666,-811
+-429,-592,574
+-355,545,-477
+703,-491,-529
+-328,-685,520
+413,935,-424
+-391,539,-444
+586,-435,557
+-364,-763,-893
+807,-499,-711
+755,-354,-619
+553,889,-390
+
+--- scanner 2 ---
+649,640,665
+682,-795,504
+-784,533,-524
+-644,584,-595
+-588,-843,648
+-30,6,44
+-674,560,763
+500,723,-460
+609,671,-379
+-555,-800,653
+-675,-892,-343
+697,-426,-610
+578,704,681
+493,664,-388
+-671,-858,530
+-667,343,800
+571,-461,-707
+-138,-166,112
+-889,563,-600
+646,-828,498
+640,759,510
+-630,509,768
+-681,-892,-333
+673,-379,-804
+-742,-814,-386
+577,-820,562
+
+--- scanner 3 ---
+-589,542,597
+605,-692,669
+-500,565,-823
+-660,373,557
+-458,-679,-417
+-488,449,543
+-626,468,-788
+338,-750,-386
+528,-832,-391
+562,-778,733
+-938,-730,414
+543,643,-506
+-524,371,-870
+407,773,750
+-104,29,83
+378,-903,-323
+-778,-728,485
+426,699,580
+-438,-605,-362
+-469,-447,-387
+509,732,623
+647,635,-688
+-868,-804,481
+614,-800,639
+595,780,-596
+
+--- scanner 4 ---
+727,592,562
+-293,-554,779
+441,611,-461
+-714,465,-776
+-743,427,-804
+-660,-479,-426
+832,-632,460
+927,-485,-438
+408,393,-506
+466,436,-512
+110,16,151
+-258,-428,682
+-393,719,612
+-211,-452,876
+808,-476,-593
+-575,615,604
+-485,667,467
+-680,325,-822
+-627,-443,-432
+872,-547,-609
+833,512,582
+807,604,487
+839,-516,451
+891,-625,532
+-652,-548,-490
+30,-46,-14";
+
+    /// Beacons spread over a 2000 cube, from a fixed linear congruential generator.
+    fn beacons(n: usize) -> Vec<Coord> {
+        let mut state = 12345u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as i64 % 1000
+        };
+        (0..n).map(|_| Coord([next(), next(), next()])).collect()
+    }
+
+    /// The report of a scanner at `position` turned by `transform`.
+    fn report(number: usize, beacons: &[Coord], position: Coord, transform: Coord) -> String {
+        let mut res = format!("--- scanner {} ---\n", number);
+        for b in beacons {
+            let c = (*b - position).transform(&transform);
+            res.push_str(&format!("{},{},{}\n", c.0[0], c.0[1], c.0[2]));
+        }
+        res
+    }
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day19>(SAMPLE, 79, 3621);
+    }
+
+    #[test]
+    fn reports_unconnected_scanners() {
+        let all = beacons(40);
+        let input = [
+            report(0, &all[..20], Coord([0, 0, 0]), Coord([1, 2, 3])),
+            report(1, &all[20..], Coord([5, 5, 5]), Coord([1, 2, 3])),
+        ]
+        .join("\n");
+        assert!(matches!(
+            Day19::parse(&input),
+            Err(Error::Solve { day: 19, .. })
+        ));
+    }
+}
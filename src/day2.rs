@@ -0,0 +1,146 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::num::ParseIntError;
+
+pub struct Day2;
+
+pub enum Dir {
+    F(i32),
+    U(i32),
+    D(i32),
+}
+
+#[derive(Debug)]
+pub enum ParseDirError {
+    NoInt(ParseIntError),
+    InvalidDirection(String),
+    NoSpace,
+}
+
+impl From<ParseIntError> for ParseDirError {
+    fn from(e: ParseIntError) -> Self {
+        Self::NoInt(e)
+    }
+}
+
+impl std::fmt::Display for ParseDirError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoInt(e) => write!(f, "invalid distance: {}", e),
+            Self::InvalidDirection(d) => write!(f, "invalid direction: {}", d),
+            Self::NoSpace => write!(f, "missing space"),
+        }
+    }
+}
+
+impl std::str::FromStr for Dir {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (dir, dist) = s.split_once(' ').ok_or(ParseDirError::NoSpace)?;
+        let dist: i32 = dist.parse()?;
+        match dir {
+            "forward" => Ok(Dir::F(dist)),
+            "up" => Ok(Dir::U(dist)),
+            "down" => Ok(Dir::D(dist)),
+            _ => Err(ParseDirError::InvalidDirection(dir.to_string())),
+        }
+    }
+}
+
+struct Pos1 {
+    h: i32,
+    v: i32,
+}
+impl Pos1 {
+    fn new() -> Self {
+        Self { h: 0, v: 0 }
+    }
+    fn do_move(self, dir: &Dir) -> Self {
+        match dir {
+            Dir::F(dist) => Self {
+                h: self.h + dist,
+                ..self
+            },
+            Dir::U(dist) => Self {
+                v: self.v - dist,
+                ..self
+            },
+            Dir::D(dist) => Self {
+                v: self.v + dist,
+                ..self
+            },
+        }
+    }
+    fn result(&self) -> i32 {
+        self.h * self.v
+    }
+}
+struct Pos2 {
+    h: i32,
+    v: i32,
+    aim: i32,
+}
+impl Pos2 {
+    fn new() -> Self {
+        Self { h: 0, v: 0, aim: 0 }
+    }
+    fn do_move(self, dir: &Dir) -> Self {
+        match dir {
+            Dir::F(dist) => Self {
+                h: self.h + dist,
+                v: self.v + self.aim * dist,
+                ..self
+            },
+            Dir::U(dist) => Self {
+                aim: self.aim - dist,
+                ..self
+            },
+            Dir::D(dist) => Self {
+                aim: self.aim + dist,
+                ..self
+            },
+        }
+    }
+    fn result(&self) -> i32 {
+        self.h * self.v
+    }
+}
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    type Model = Vec<Dir>;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        input
+            .lines()
+            .map(|l| l.parse().map_err(|e| Error::parse(Self::DAY, e)))
+            .collect()
+    }
+
+    fn part1(dirs: &Self::Model) -> Result<Answer, Error> {
+        Ok(dirs.iter().fold(Pos1::new(), Pos1::do_move).result().into())
+    }
+
+    fn part2(dirs: &Self::Model) -> Result<Answer, Error> {
+        Ok(dirs.iter().fold(Pos2::new(), Pos2::do_move).result().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day2>(SAMPLE, 150, 900);
+    }
+}
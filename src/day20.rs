@@ -0,0 +1,93 @@
+use crate::grid::{Grid, Pos};
+use crate::solution::{Answer, Solution};
+use crate::Error;
+
+pub struct Day20;
+
+type Image = Grid<bool>;
+
+/// The enhanced pixel at `(x, y)` of the image grown by one on each side.
+fn get_pixel(image: &Image, algo: &[bool], (x, y): Pos, outside: bool) -> bool {
+    let mut index = 0;
+    for dy in [-2, -1, 0] {
+        for dx in [-2, -1, 0] {
+            index <<= 1;
+            let val = image
+                .offset((x, y), (dx, dy))
+                .map(|pos| image[pos])
+                .unwrap_or(outside);
+            if val {
+                index |= 1;
+            }
+        }
+    }
+    algo[index]
+}
+
+fn enhance(image: &Image, algo: &[bool], outside: bool) -> Image {
+    Grid::from_fn(image.width() + 2, image.height() + 2, |pos| {
+        get_pixel(image, algo, pos, outside)
+    })
+}
+
+fn light_after(algo: &[bool], image: &Image, steps: usize) -> usize {
+    let mut image = image.clone();
+    for _ in 0..steps / 2 {
+        image = enhance(&image, algo, false);
+        image = enhance(&image, algo, algo[0]);
+    }
+    image.values().filter(|&&x| x).count()
+}
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+    type Model = (Vec<bool>, Image);
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        let mut lines = input.lines().map(str::trim);
+        let algo = lines
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .map(|c| c == '#')
+            .collect::<Vec<_>>();
+        if algo.len() != 512 {
+            return Err(Error::parse(Self::DAY, "algorithm must have 512 entries"));
+        }
+        let image = lines.skip_while(|l| l.is_empty()).collect::<Vec<_>>();
+        let image = Grid::parse(&image.join("\n"), |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .map_err(|e| Error::parse(Self::DAY, e))?;
+        Ok((algo, image))
+    }
+
+    fn part1((algo, image): &Self::Model) -> Result<Answer, Error> {
+        Ok(light_after(algo, image, 2).into())
+    }
+
+    fn part2((algo, image): &Self::Model) -> Result<Answer, Error> {
+        Ok(light_after(algo, image, 50).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day20>(SAMPLE, 35, 3351);
+    }
+}
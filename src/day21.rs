@@ -0,0 +1,172 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::{
+    collections::HashMap,
+    num::ParseIntError,
+    ops::{Add, AddAssign},
+    str::FromStr,
+};
+
+pub struct Day21;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mod10(usize);
+
+impl FromStr for Mod10 {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+impl Add for Mod10 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self((self.0 + rhs.0 - 1) % 10 + 1)
+    }
+}
+
+impl AddAssign for Mod10 {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 = (self.0 + rhs.0 - 1) % 10 + 1;
+    }
+}
+
+impl Mod10 {
+    fn new(v: usize) -> Self {
+        Self((v - 1) % 10 + 1)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+enum GameState {
+    Running { pos: [Mod10; 2], score: [usize; 2] },
+    Win(usize),
+}
+
+impl GameState {
+    fn new(start1: usize, start2: usize) -> Self {
+        Self::Running {
+            pos: [Mod10::new(start1), Mod10::new(start2)],
+            score: [0, 0],
+        }
+    }
+
+    fn next_states(&self, who: usize) -> HashMap<GameState, usize> {
+        const DICE_SUM_WAYS: [usize; 10] = [0, 0, 0, 1, 3, 6, 7, 6, 3, 1];
+        let mut res = HashMap::new();
+        match self {
+            Self::Running { pos, score } => {
+                for (dice_result, count) in DICE_SUM_WAYS.iter().enumerate().skip(3) {
+                    let mut new_pos = *pos;
+                    let mut new_score = *score;
+                    new_pos[who] += Mod10::new(dice_result);
+                    new_score[who] += new_pos[who].0;
+                    let new_state = if new_score[who] < 21 {
+                        Self::Running {
+                            pos: new_pos,
+                            score: new_score,
+                        }
+                    } else {
+                        Self::Win(who)
+                    };
+                    *res.entry(new_state).or_insert(0) += *count;
+                }
+            }
+            _ => {
+                res.insert(self.clone(), 1);
+            }
+        }
+        res
+    }
+
+    fn is_over(&self) -> bool {
+        !matches!(self, Self::Running { .. })
+    }
+}
+
+fn roll_three(die: &mut impl Iterator<Item = Mod10>, roll: &mut usize) -> Mod10 {
+    let mut s = Mod10::new(10);
+    for d in die.take(3) {
+        s += d;
+        *roll += 1;
+    }
+    s
+}
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+    /// Starting positions of both players.
+    type Model = (usize, usize);
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        let mut starts = input.lines().map(|l| {
+            l.split_once(':')
+                .and_then(|(_, v)| v.trim().parse::<usize>().ok())
+                .filter(|v| *v > 0)
+                .ok_or_else(|| Error::parse(Self::DAY, format!("invalid start: {}", l)))
+        });
+        match (starts.next(), starts.next()) {
+            (Some(p1), Some(p2)) => Ok((p1?, p2?)),
+            _ => Err(Error::parse(Self::DAY, "expected two players")),
+        }
+    }
+
+    fn part1(&(p1_start, p2_start): &Self::Model) -> Result<Answer, Error> {
+        let mut p1 = Mod10::new(p1_start);
+        let mut p2 = Mod10::new(p2_start);
+        let mut score1 = 0;
+        let mut score2 = 0;
+
+        let mut die = (1..=100).cycle().map(Mod10::new);
+        let mut roll = 0;
+
+        let res1 = loop {
+            p1 += roll_three(&mut die, &mut roll);
+            score1 += p1.0;
+            if score1 >= 1000 {
+                break score2 * roll;
+            }
+
+            p2 += roll_three(&mut die, &mut roll);
+            score2 += p2.0;
+            if score2 >= 1000 {
+                break score1 * roll;
+            }
+        };
+        Ok(res1.into())
+    }
+
+    fn part2(&(p1_start, p2_start): &Self::Model) -> Result<Answer, Error> {
+        let mut state_count = HashMap::new();
+        state_count.insert(GameState::new(p1_start, p2_start), 1);
+        let mut who = 0;
+        while state_count.keys().any(|s| !s.is_over()) {
+            let mut new_state_count = HashMap::new();
+            for (state, count) in state_count.iter() {
+                for (next_state, next_count) in state.next_states(who) {
+                    *new_state_count.entry(next_state).or_insert(0) += count * next_count;
+                }
+            }
+            state_count = new_state_count;
+            who = 1 - who;
+        }
+        Ok(state_count.values().max().copied().unwrap_or(0).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "Player 1 starting position: 4
+Player 2 starting position: 8";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day21>(SAMPLE, 739785, 444356092776315u64);
+    }
+}
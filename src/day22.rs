@@ -0,0 +1,193 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::str::FromStr;
+
+pub struct Day22;
+
+#[derive(Debug, Clone)]
+pub struct Step {
+    turn_on: bool,
+    range: [(i64, i64); 3],
+}
+
+impl FromStr for Step {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::parse(Day22::DAY, format!("invalid step: {}", s));
+        let (on, rest) = s.split_once(' ').ok_or_else(invalid)?;
+        let turn_on = on == "on";
+        let mut range: [(i64, i64); 3] = Default::default();
+        let mut axes = rest.split(',');
+        for r in range.iter_mut() {
+            let (min, max) = axes
+                .next()
+                .and_then(|a| a.split_once('='))
+                .and_then(|(_, val)| val.split_once(".."))
+                .ok_or_else(invalid)?;
+            let parse = |v: &str| v.trim().parse::<i64>().map_err(|_| invalid());
+            *r = (parse(min)?, parse(max)? + 1);
+        }
+
+        Ok(Self { turn_on, range })
+    }
+}
+
+impl Step {
+    /// Restricts the step to the initialization area -50..=50. The upper
+    /// bound of `range` is exclusive.
+    fn limit(mut self) -> Self {
+        for (min, max) in self.range.iter_mut() {
+            *min = (*min).max(-50);
+            *max = (*max).min(51);
+        }
+        self
+    }
+
+    fn intersects(&self, other: &Self) -> bool {
+        for (s, o) in self.range.iter().zip(&other.range) {
+            if s.0 >= o.1 || s.1 <= o.0 {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn volume(&self) -> i64 {
+        self.range
+            .iter()
+            .map(|(min, max)| (max - min).max(0))
+            .product()
+    }
+
+    fn apply_vec(self, v: &mut Vec<Step>) {
+        if self.turn_on {
+            self.union_vec(v);
+        } else {
+            self.difference_vec(v);
+        }
+    }
+
+    fn union_vec(self, v: &mut Vec<Step>) {
+        assert!(self.turn_on);
+        let inter_idx = v.iter().position(|x| self.intersects(x));
+        if let Some(inter_idx) = inter_idx {
+            let inter = v.swap_remove(inter_idx);
+            for n in inter.union(self) {
+                n.union_vec(v);
+            }
+        } else {
+            v.push(self);
+        }
+    }
+    fn difference_vec(self, v: &mut Vec<Step>) {
+        assert!(!self.turn_on);
+        while let Some(inter_idx) = v.iter().position(|x| self.intersects(x)) {
+            let inter = v.swap_remove(inter_idx);
+            v.append(&mut inter.difference(self.clone()));
+        }
+    }
+
+    fn union(self, other: Self) -> Vec<Self> {
+        assert!(self.turn_on);
+        assert!(other.turn_on);
+        let mut res = Vec::new();
+        if !self.intersects(&other) {
+            res.push(self);
+            res.push(other);
+            return res;
+        }
+        let (smaller, larger) = if self.volume() > other.volume() {
+            (other, self)
+        } else {
+            (self, other)
+        };
+        for i in 0..smaller.range.len() {
+            let mut new_step = smaller.clone();
+            new_step.range[i].1 = larger.range[i].0;
+            if new_step.volume() > 0 {
+                new_step.union_vec(&mut res);
+            }
+            let mut new_step = smaller.clone();
+            new_step.range[i].0 = larger.range[i].1;
+            if new_step.volume() > 0 {
+                new_step.union_vec(&mut res);
+            }
+        }
+        res.push(larger);
+        res
+    }
+    fn difference(self, other: Self) -> Vec<Self> {
+        assert!(self.turn_on);
+        assert!(!other.turn_on);
+        let mut res = Vec::new();
+        if !self.intersects(&other) {
+            res.push(self);
+            return res;
+        }
+        let (smaller, larger) = (self, other);
+        for i in 0..smaller.range.len() {
+            let mut new_step = smaller.clone();
+            new_step.range[i].1 = larger.range[i].0;
+            if new_step.volume() > 0 {
+                new_step.union_vec(&mut res);
+            }
+            let mut new_step = smaller.clone();
+            new_step.range[i].0 = larger.range[i].1;
+            if new_step.volume() > 0 {
+                new_step.union_vec(&mut res);
+            }
+        }
+
+        res
+    }
+}
+fn lit_volume(sequence: impl Iterator<Item = Step>) -> i64 {
+    let mut res = Vec::new();
+    for s in sequence {
+        s.apply_vec(&mut res);
+    }
+    res.iter().map(|x| x.volume()).sum()
+}
+
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+    type Model = Vec<Step>;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        input.lines().map(|l| l.trim().parse()).collect()
+    }
+
+    fn part1(sequence: &Self::Model) -> Result<Answer, Error> {
+        Ok(lit_volume(sequence.iter().map(|s| s.clone().limit())).into())
+    }
+
+    fn part2(sequence: &Self::Model) -> Result<Answer, Error> {
+        Ok(lit_volume(sequence.iter().cloned()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day22>(SAMPLE, 39, 39);
+    }
+
+    #[test]
+    fn limits_to_initialization_area() {
+        assert_answers::<Day22>(
+            "on x=-60..60,y=0..0,z=0..0\noff x=0..0,y=0..0,z=0..0",
+            100,
+            120,
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::search;
+use crate::solution::{Answer, Solution};
+use crate::Error;
+
+pub struct Day23;
+
+const COST_MAP: [usize; 4] = [1, 10, 100, 1000];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct State {
+    holes: [[Option<usize>; 4]; 4],
+    corridor: [Option<usize>; 7],
+}
+
+fn to_real_pos(pos: usize) -> usize {
+    if pos < 2 {
+        pos
+    } else if pos > 4 {
+        pos + 4
+    } else {
+        2 * pos - 1
+    }
+}
+
+impl State {
+    fn get_next(&self) -> Vec<(State, usize)> {
+        let mut res = Vec::new();
+        // find possible moves out of holes
+        for (i, v) in self.holes.iter().enumerate() {
+            if v.iter().all(|x| *x == Some(i) || x.is_none()) {
+                // Hole correctly filled. ignore
+                continue;
+            }
+            if let Some((hp, &hv)) = v
+                .iter()
+                .enumerate()
+                .find_map(|(p, v)| Some((p, v.as_ref()?)))
+            {
+                for f in self.find_free_left(i + 2) {
+                    let mut new_state = self.clone();
+                    new_state.holes[i][hp] = None;
+                    new_state.corridor[f] = Some(hv);
+                    let cost = to_real_pos(i + 2) - to_real_pos(f) + hp;
+                    res.push((new_state, cost * COST_MAP[hv]));
+                }
+                for f in self.find_free_right(i + 2) {
+                    let mut new_state = self.clone();
+                    new_state.holes[i][hp] = None;
+                    new_state.corridor[f] = Some(hv);
+                    let cost = to_real_pos(f) - to_real_pos(i + 2) + 2 + hp;
+                    res.push((new_state, cost * COST_MAP[hv]));
+                }
+            }
+        }
+        // find possible moves into holes
+        for (i, v) in self.corridor.iter().enumerate() {
+            if let Some(v) = v {
+                let hole_idx = *v;
+                let hv = self.holes[hole_idx];
+                if hv.iter().filter_map(|x| *x).any(|x| x != hole_idx) {
+                    //skip if there are wrong values in the hole
+                    continue;
+                }
+                let hole_depth = hv.iter().position(Option::is_some).unwrap_or(hv.len());
+                if hole_depth == 0 {
+                    continue;
+                }
+                if i < hole_idx + 2 {
+                    if self.corridor[i + 1..hole_idx + 2]
+                        .iter()
+                        .all(|x| x.is_none())
+                    {
+                        let mut new_state = self.clone();
+                        new_state.corridor[i] = None;
+                        new_state.holes[hole_idx][hole_depth - 1] = Some(*v);
+                        let cost = to_real_pos(hole_idx + 2) - to_real_pos(i) + hole_depth - 1;
+                        res.push((new_state, cost * COST_MAP[*v]));
+                    }
+                } else if self.corridor[hole_idx + 2..i].iter().all(|x| x.is_none()) {
+                    let mut new_state = self.clone();
+                    new_state.corridor[i] = None;
+                    new_state.holes[hole_idx][hole_depth - 1] = Some(*v);
+                    let cost = to_real_pos(i) - to_real_pos(hole_idx + 2) + hole_depth + 1;
+                    res.push((new_state, cost * COST_MAP[*v]));
+                }
+            }
+        }
+        res
+    }
+
+    fn find_free_left(&self, pos: usize) -> Vec<usize> {
+        (0..pos)
+            .rev()
+            .take_while(|x| self.corridor[*x].is_none())
+            .collect()
+    }
+    fn find_free_right(&self, pos: usize) -> Vec<usize> {
+        (pos..7)
+            .take_while(|x| self.corridor[*x].is_none())
+            .collect()
+    }
+    fn is_finished(&self) -> bool {
+        self.holes
+            .iter()
+            .enumerate()
+            .all(|(i, x)| x.iter().all(|x| *x == Some(i)))
+    }
+}
+
+fn least_energy(start: &State) -> Result<Answer, Error> {
+    search::dijkstra(start.clone(), State::get_next, State::is_finished)
+        .map(|found| found.cost.into())
+        .ok_or_else(|| Error::solve(Day23::DAY, "amphipods cannot be organized"))
+}
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+    /// The burrow with the two rows of the input at the top of each hole.
+    type Model = State;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        let mut lines = input.lines();
+        lines.next(); //skip wall
+        if lines
+            .next()
+            .map(|l| l.chars().filter(|x| *x == '.').count())
+            != Some(7 + 4)
+        {
+            return Err(Error::parse(Self::DAY, "corridor must have 11 spaces"));
+        }
+        let mut start = State {
+            holes: Default::default(),
+            corridor: Default::default(),
+        };
+        for j in 0..=1 {
+            let line = lines.next().unwrap_or_default();
+            let cells: Vec<char> = line.trim().chars().filter(|x| *x != '#').collect();
+            if cells.len() != 4 {
+                return Err(Error::parse(Self::DAY, format!("invalid row: {}", line)));
+            }
+            for (i, c) in cells.into_iter().enumerate() {
+                if !('A'..='D').contains(&c) {
+                    return Err(Error::parse(Self::DAY, format!("invalid amphipod: {}", c)));
+                }
+                start.holes[i][j] = Some(c as usize - 'A' as usize);
+            }
+        }
+        Ok(start)
+    }
+
+    fn part1(start: &Self::Model) -> Result<Answer, Error> {
+        let mut start = start.clone();
+        for j in 2..=3 {
+            for (i, h) in start.holes.iter_mut().enumerate() {
+                h[j] = Some(i);
+            }
+        }
+        least_energy(&start)
+    }
+
+    fn part2(start: &Self::Model) -> Result<Answer, Error> {
+        const MIDDLE: [[usize; 2]; 4] = [[3, 3], [2, 1], [1, 0], [0, 2]];
+        let mut start = start.clone();
+        for (base, middle) in start.holes.iter_mut().zip(MIDDLE.iter()) {
+            base[3] = base[1];
+            base[1] = Some(middle[0]);
+            base[2] = Some(middle[1]);
+        }
+        least_energy(&start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day23>(SAMPLE, 12521, 44169);
+    }
+}
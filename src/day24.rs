@@ -0,0 +1,220 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+
+pub struct Day24;
+
+fn invalid(what: &str, s: &str) -> Error {
+    Error::parse(Day24::DAY, format!("invalid {}: {}", what, s))
+}
+
+#[derive(Debug)]
+pub enum Reg {
+    W,
+    X,
+    Y,
+    Z,
+}
+impl std::str::FromStr for Reg {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "w" => Self::W,
+            "x" => Self::X,
+            "y" => Self::Y,
+            "z" => Self::Z,
+            _ => return Err(invalid("register", s)),
+        })
+    }
+}
+#[derive(Debug)]
+pub enum Val {
+    R(Reg),
+    I(i64),
+}
+impl std::str::FromStr for Val {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.parse::<i64>() {
+            Ok(v) => Self::I(v),
+            Err(_) => Self::R(s.parse()?),
+        })
+    }
+}
+#[derive(Debug)]
+pub enum Command {
+    Inp(Reg),
+    Add(Reg, Val),
+    Mul(Reg, Val),
+    Div(Reg, Val),
+    Mod(Reg, Val),
+    Eql(Reg, Val),
+}
+impl std::str::FromStr for Command {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (cmd, args) = s.split_once(' ').ok_or_else(|| invalid("command", s))?;
+        if cmd == "inp" {
+            return Ok(Self::Inp(args.parse()?));
+        }
+        let (arg1, arg2) = args.split_once(' ').ok_or_else(|| invalid("command", s))?;
+        let arg1 = arg1.parse()?;
+        let arg2 = arg2.parse()?;
+        Ok(match cmd {
+            "add" => Self::Add(arg1, arg2),
+            "mul" => Self::Mul(arg1, arg2),
+            "div" => Self::Div(arg1, arg2),
+            "mod" => Self::Mod(arg1, arg2),
+            "eql" => Self::Eql(arg1, arg2),
+            _ => return Err(invalid("command", s)),
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct Alu {
+    w: i64,
+    x: i64,
+    y: i64,
+    z: i64,
+    input: [i64; 14],
+    input_index: usize,
+}
+
+impl Alu {
+    fn new(input: [i64; 14]) -> Self {
+        Self {
+            input,
+            ..Default::default()
+        }
+    }
+    fn execute(&mut self, cmd: &Command) -> Option<()> {
+        match cmd {
+            Command::Add(t, v) => self.set_reg(t, self.get_reg(t) + self.get_val(v)),
+            Command::Mul(t, v) => self.set_reg(t, self.get_reg(t) * self.get_val(v)),
+            Command::Div(t, v) => self.set_reg(t, self.get_reg(t).checked_div(self.get_val(v))?),
+            Command::Mod(t, v) => self.set_reg(t, self.get_reg(t).checked_rem(self.get_val(v))?),
+            Command::Eql(t, v) => self.set_reg(t, (self.get_reg(t) == self.get_val(v)) as i64),
+            Command::Inp(r) => {
+                self.set_reg(r, *self.input.get(self.input_index)?);
+                self.input_index += 1;
+            }
+        }
+        Some(())
+    }
+
+    fn get_val(&self, v: &Val) -> i64 {
+        match v {
+            Val::I(x) => *x,
+            Val::R(r) => self.get_reg(r),
+        }
+    }
+    fn get_reg(&self, r: &Reg) -> i64 {
+        match r {
+            Reg::W => self.w,
+            Reg::X => self.x,
+            Reg::Y => self.y,
+            Reg::Z => self.z,
+        }
+    }
+
+    fn set_reg(&mut self, r: &Reg, val: i64) {
+        match r {
+            Reg::W => self.w = val,
+            Reg::X => self.x = val,
+            Reg::Y => self.y = val,
+            Reg::Z => self.z = val,
+        }
+    }
+}
+
+/// Runs the program on the model number and returns it if it is valid.
+///
+/// The numbers were found by analyzing the program by hand, this only checks them.
+fn check(cmds: &[Command], input: [i64; 14]) -> Result<Answer, Error> {
+    let mut alu = Alu::new(input);
+    for c in cmds {
+        alu.execute(c)
+            .ok_or_else(|| Error::solve(Day24::DAY, "program failed"))?;
+    }
+    if alu.z != 0 {
+        return Err(Error::solve(
+            Day24::DAY,
+            format!("model number is not valid, z = {}", alu.z),
+        ));
+    }
+    Ok(alu.input.iter().fold(0, |a, d| a * 10 + d).into())
+}
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+    type Model = Vec<Command>;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.trim().parse())
+            .collect()
+    }
+
+    fn part1(cmds: &Self::Model) -> Result<Answer, Error> {
+        check(cmds, [1, 2, 9, 9, 6, 9, 9, 7, 8, 2, 9, 3, 9, 9]) // largest
+    }
+
+    fn part2(cmds: &Self::Model) -> Result<Answer, Error> {
+        check(cmds, [1, 1, 8, 4, 1, 2, 3, 1, 1, 1, 7, 1, 8, 9]) // smallest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(program: &str, input: &[i64]) -> Alu {
+        let mut digits = [0; 14];
+        digits[..input.len()].copy_from_slice(input);
+        let mut alu = Alu::new(digits);
+        for c in Day24::parse(program).unwrap() {
+            alu.execute(&c).unwrap();
+        }
+        alu
+    }
+
+    #[test]
+    fn executes_sample_programs() {
+        let alu = run("inp x\nmul x -1", &[7]);
+        assert_eq!(alu.x, -7);
+
+        let alu = run("inp z\ninp x\nmul z 3\neql z x", &[2, 6]);
+        assert_eq!(alu.z, 1);
+
+        let binary = "inp w
+add z w
+mod z 2
+div w 2
+add y w
+mod y 2
+div w 2
+add x w
+mod x 2
+div w 2
+mod w 2";
+        let alu = run(binary, &[13]);
+        assert_eq!((alu.w, alu.x, alu.y, alu.z), (1, 1, 0, 1));
+    }
+
+    #[test]
+    fn rejects_invalid_model_numbers() {
+        let cmds = Day24::parse("inp z").unwrap();
+        assert!(check(&cmds, [0; 14]).is_ok());
+        assert!(matches!(
+            check(&cmds, [1; 14]),
+            Err(Error::Solve { day: 24, .. })
+        ));
+        assert!(Day24::parse("div q 2").is_err());
+        assert!(check(&Day24::parse("div z 0").unwrap(), [0; 14]).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
+use crate::Error;
+
+pub struct Day25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+impl Cell {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Empty),
+            '>' => Some(Self::East),
+            'v' => Some(Self::South),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Self::Empty => '.',
+            Self::East => '>',
+            Self::South => 'v',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// The sea floor with both herds of sea cucumbers. It wraps around at the edges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeaFloor {
+    cells: Grid<Cell>,
+}
+
+impl std::str::FromStr for SeaFloor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cells = Grid::parse(s, Cell::from_char).map_err(|e| Error::parse(Day25::DAY, e))?;
+        Ok(Self {
+            cells: cells.wrapping(true),
+        })
+    }
+}
+
+impl std::fmt::Display for SeaFloor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.cells)
+    }
+}
+
+impl SeaFloor {
+    /// Moves every member of `herd` whose neighbor in `dir` is free, all at the same time.
+    fn move_herd(&mut self, herd: Cell, dir: (isize, isize)) -> usize {
+        let moves: Vec<_> = self
+            .cells
+            .iter()
+            .filter(|(_, c)| **c == herd)
+            .filter_map(|(pos, _)| Some((pos, self.cells.offset(pos, dir)?)))
+            .filter(|(_, to)| self.cells[*to] == Cell::Empty)
+            .collect();
+        for &(from, to) in &moves {
+            self.cells[from] = Cell::Empty;
+            self.cells[to] = herd;
+        }
+        moves.len()
+    }
+
+    /// Moves the east-facing herd, then the south-facing one, and returns how
+    /// many sea cucumbers moved.
+    pub fn step(&mut self) -> usize {
+        self.move_herd(Cell::East, (1, 0)) + self.move_herd(Cell::South, (0, 1))
+    }
+
+    /// The states after each step, up to and including the first step in which
+    /// nothing moves.
+    pub fn steps(&self) -> Steps {
+        Steps {
+            floor: Some(self.clone()),
+        }
+    }
+
+    /// Number of the first step in which no sea cucumber moves.
+    pub fn steps_until_stable(&self) -> usize {
+        self.steps().count()
+    }
+}
+
+/// Iterator over the states of a [`SeaFloor`], see [`SeaFloor::steps`].
+pub struct Steps {
+    floor: Option<SeaFloor>,
+}
+
+impl Iterator for Steps {
+    type Item = SeaFloor;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut floor = self.floor.take()?;
+        if floor.step() > 0 {
+            self.floor = Some(floor.clone());
+        }
+        Some(floor)
+    }
+}
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+    type Model = SeaFloor;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        input.parse()
+    }
+
+    fn part1(floor: &Self::Model) -> Result<Answer, Error> {
+        Ok(floor.steps_until_stable().into())
+    }
+
+    /// Day 25 has no second puzzle, the star is awarded for all others.
+    fn part2(_: &Self::Model) -> Result<Answer, Error> {
+        Ok("Merry Christmas".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day25>(SAMPLE, 58, "Merry Christmas");
+    }
+
+    #[test]
+    fn moves_herds() {
+        let floor: SeaFloor = "...>>>>>...".parse().unwrap();
+        let states: Vec<_> = floor.steps().take(2).map(|f| f.to_string()).collect();
+        assert_eq!(states, ["...>>>>.>..\n", "...>>>.>.>.\n"]);
+
+        let floor: SeaFloor = "..........
+.>v....v..
+.......>..
+.........."
+            .parse()
+            .unwrap();
+        assert_eq!(
+            floor.steps().next().unwrap().to_string(),
+            "..........
+.>........
+..v....v>.
+..........
+"
+        );
+
+        let wrapping: SeaFloor = "...>...
+.......
+......>
+v.....>
+......>
+.......
+..vvv.."
+            .parse()
+            .unwrap();
+        assert_eq!(
+            wrapping.steps().nth(3).unwrap().to_string(),
+            ">......
+..v....
+..>.v..
+.>.v...
+...>...
+.......
+v......
+"
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+
+pub struct Day3;
+
+#[derive(Debug, Clone)]
+pub struct Bindata(Vec<bool>);
+
+#[derive(Debug)]
+pub enum BinParseError {
+    InvalidDigit(char),
+}
+
+impl std::fmt::Display for BinParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDigit(c) => write!(f, "invalid binary digit: {}", c),
+        }
+    }
+}
+
+impl From<&Bindata> for u32 {
+    fn from(d: &Bindata) -> Self {
+        d.0.iter().fold(0, |a, b| a * 2 + (*b as u32))
+    }
+}
+
+impl std::str::FromStr for Bindata {
+    type Err = BinParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut res = Vec::new();
+        for c in s.chars() {
+            match c {
+                '1' => res.push(true),
+                '0' => res.push(false),
+                _ => return Err(BinParseError::InvalidDigit(c)),
+            }
+        }
+        Ok(Self(res))
+    }
+}
+
+fn count_ones(data: &[Bindata], pos: usize) -> usize {
+    data.iter().filter(|d| d.0[pos]).count()
+}
+
+fn filter_oxygen(data: &mut Vec<Bindata>, pos: usize) {
+    if data.len() != 1 {
+        let threshold = data.len().div_ceil(2);
+        let one_count = count_ones(data, pos);
+        data.retain(|d| d.0[pos] == (one_count >= threshold))
+    }
+}
+fn filter_co2(data: &mut Vec<Bindata>, pos: usize) {
+    if data.len() != 1 {
+        let threshold = data.len().div_ceil(2);
+        let one_count = count_ones(data, pos);
+        data.retain(|d| d.0[pos] != (one_count >= threshold))
+    }
+}
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    type Model = Vec<Bindata>;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        let data: Vec<Bindata> = input
+            .lines()
+            .map(|l| l.parse().map_err(|e| Error::parse(Self::DAY, e)))
+            .collect::<Result<_, _>>()?;
+        if data.is_empty() {
+            return Err(Error::parse(Self::DAY, "no numbers"));
+        }
+        Ok(data)
+    }
+
+    fn part1(data: &Self::Model) -> Result<Answer, Error> {
+        let threshold = data.len() / 2;
+        let mut gamma = 0u32;
+        let mut epsilon = 0u32;
+        for i in 0..data[0].0.len() {
+            gamma *= 2;
+            epsilon *= 2;
+            if count_ones(data, i) > threshold {
+                gamma += 1;
+            } else {
+                epsilon += 1;
+            }
+        }
+        Ok((gamma * epsilon).into())
+    }
+
+    fn part2(data: &Self::Model) -> Result<Answer, Error> {
+        let mut oxygen = data.clone();
+        let mut co2 = data.clone();
+        for i in 0..data[0].0.len() {
+            filter_oxygen(&mut oxygen, i);
+            filter_co2(&mut co2, i);
+        }
+        if oxygen.len() != 1 || co2.len() != 1 {
+            return Err(Error::solve(Self::DAY, "ratings are not unique"));
+        }
+        let oxy_value: u32 = (&oxygen[0]).into();
+        let co2_value: u32 = (&co2[0]).into();
+        Ok((oxy_value * co2_value).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day3>(SAMPLE, 198, 230);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::collections::HashSet;
+
+pub struct Day4;
+
+#[derive(Debug, Clone)]
+pub struct Board {
+    lines: [HashSet<u32>; 10],
+}
+impl Board {
+    fn new<'a>(data: &mut impl Iterator<Item = &'a str>) -> Result<Option<Self>, Error> {
+        let mut lines: [HashSet<u32>; 10] = Default::default();
+        for i in 0..5 {
+            if let Some(d) = data.next() {
+                let line: Vec<u32> = d
+                    .split_ascii_whitespace()
+                    .map(|s| s.parse().map_err(|e| Error::parse(Day4::DAY, e)))
+                    .collect::<Result<_, _>>()?;
+                lines[i] = line.iter().copied().collect();
+                for (j, v) in line.iter().enumerate().take(5) {
+                    lines[5 + j].insert(*v);
+                }
+            } else if i == 0 {
+                return Ok(None);
+            } else {
+                return Err(Error::parse(Day4::DAY, "incomplete board"));
+            }
+        }
+        if lines.iter().any(|l| l.len() != 5) {
+            return Err(Error::parse(Day4::DAY, "board is not 5x5"));
+        }
+        Ok(Some(Self { lines }))
+    }
+    fn mark(&mut self, number: u32) -> Option<u32> {
+        for l in self.lines.iter_mut() {
+            l.remove(&number);
+            if l.is_empty() {
+                let rest_sum: u32 = self.lines[..5].iter().flatten().sum();
+                return Some(rest_sum * number);
+            }
+        }
+        None
+    }
+    fn is_finished(&self) -> bool {
+        self.lines.iter().any(|l| l.is_empty())
+    }
+}
+
+fn find_next_win(numbers: impl Iterator<Item = u32>, boards: &mut [Board]) -> Option<u32> {
+    for p in numbers {
+        let res = boards.iter_mut().filter_map(|b| b.mark(p)).last();
+        if res.is_some() {
+            return res;
+        }
+    }
+    None
+}
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    type Model = (Vec<u32>, Vec<Board>);
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        let mut lines = input.lines().filter(|l| !l.trim().is_empty());
+        let first_line = lines
+            .next()
+            .ok_or_else(|| Error::parse(Self::DAY, "no numbers"))?;
+        let picked = first_line
+            .split(',')
+            .map(|s| s.trim().parse().map_err(|e| Error::parse(Self::DAY, e)))
+            .collect::<Result<_, _>>()?;
+        let mut boards = Vec::new();
+        while let Some(b) = Board::new(&mut lines)? {
+            boards.push(b);
+        }
+        Ok((picked, boards))
+    }
+
+    fn part1((picked, boards): &Self::Model) -> Result<Answer, Error> {
+        let mut boards = boards.clone();
+        find_next_win(picked.iter().copied(), &mut boards)
+            .map(Answer::from)
+            .ok_or_else(|| Error::solve(Self::DAY, "no board wins"))
+    }
+
+    fn part2((picked, boards): &Self::Model) -> Result<Answer, Error> {
+        let mut boards = boards.clone();
+        let mut picked = picked.iter().copied();
+        let mut last = None;
+        while let Some(val) = find_next_win(&mut picked, &mut boards) {
+            last = Some(val);
+            boards.retain(|b| !b.is_finished());
+        }
+        last.map(Answer::from)
+            .ok_or_else(|| Error::solve(Self::DAY, "no board wins"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day4>(SAMPLE, 4512, 1924);
+    }
+}
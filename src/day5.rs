@@ -0,0 +1,148 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::collections::{HashMap, HashSet};
+use std::num::ParseIntError;
+
+pub struct Day5;
+
+#[derive(Debug, Clone)]
+pub struct Line {
+    pub points: HashSet<(i32, i32)>,
+}
+
+impl Line {
+    fn new(x1: i32, y1: i32, x2: i32, y2: i32) -> Self {
+        let mut points = HashSet::new();
+        if (x1 - x2).abs() > (y1 - y2).abs() {
+            let (x1, y1, x2, y2) = if x2 < x1 {
+                (x2, y2, x1, y1)
+            } else {
+                (x1, y1, x2, y2)
+            };
+            for x in x1..=x2 {
+                let y = (y2 - y1).signum() * (x - x1) + y1;
+                points.insert((x, y));
+            }
+        } else {
+            let (x1, y1, x2, y2) = if y2 < y1 {
+                (x2, y2, x1, y1)
+            } else {
+                (x1, y1, x2, y2)
+            };
+            for y in y1..=y2 {
+                let x = (x2 - x1).signum() * (y - y1) + x1;
+                points.insert((x, y));
+            }
+        }
+        Self { points }
+    }
+    fn is_horz(&self) -> bool {
+        self.points
+            .iter()
+            .map(|x| x.0)
+            .collect::<HashSet<_>>()
+            .len()
+            == 1
+    }
+    fn is_vert(&self) -> bool {
+        self.points
+            .iter()
+            .map(|x| x.1)
+            .collect::<HashSet<_>>()
+            .len()
+            == 1
+    }
+    fn is_hv(&self) -> bool {
+        self.is_horz() || self.is_vert()
+    }
+}
+#[derive(Debug)]
+pub enum ParseLineError {
+    NoArrow,
+    WrongPointFormat,
+    IntError(ParseIntError),
+}
+
+impl From<ParseIntError> for ParseLineError {
+    fn from(v: ParseIntError) -> Self {
+        Self::IntError(v)
+    }
+}
+
+impl std::fmt::Display for ParseLineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoArrow => write!(f, "missing arrow"),
+            Self::WrongPointFormat => write!(f, "point is not x,y"),
+            Self::IntError(e) => write!(f, "invalid coordinate: {}", e),
+        }
+    }
+}
+
+impl std::str::FromStr for Line {
+    type Err = ParseLineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (p1, p2) = s.split_once(" -> ").ok_or(ParseLineError::NoArrow)?;
+        let (x1, y1) = p1.split_once(',').ok_or(ParseLineError::WrongPointFormat)?;
+        let (x2, y2) = p2.split_once(',').ok_or(ParseLineError::WrongPointFormat)?;
+        Ok(Self::new(
+            x1.parse()?,
+            y1.parse()?,
+            x2.parse()?,
+            y2.parse()?,
+        ))
+    }
+}
+
+fn count_overlaps<'a>(lines: impl Iterator<Item = &'a Line>) -> usize {
+    let mut map = HashMap::new();
+    for l in lines {
+        for p in &l.points {
+            *map.entry(p).or_insert(0) += 1;
+        }
+    }
+    map.values().filter(|v| **v > 1).count()
+}
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    type Model = Vec<Line>;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        input
+            .lines()
+            .map(|l| l.parse().map_err(|e| Error::parse(Self::DAY, e)))
+            .collect()
+    }
+
+    fn part1(lines: &Self::Model) -> Result<Answer, Error> {
+        Ok(count_overlaps(lines.iter().filter(|l| l.is_hv())).into())
+    }
+
+    fn part2(lines: &Self::Model) -> Result<Answer, Error> {
+        Ok(count_overlaps(lines.iter()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day5>(SAMPLE, 5, 12);
+    }
+}
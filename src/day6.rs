@@ -0,0 +1,65 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+
+pub struct Day6;
+
+fn advance(state: &mut [u64], spawn_index: &mut usize) {
+    let cycle_index = (*spawn_index + 7) % 9;
+    state[cycle_index] += state[*spawn_index];
+    *spawn_index = (*spawn_index + 1) % 9;
+}
+
+fn simulate(initial: &[u64; 9], days: usize) -> u64 {
+    let mut state = *initial;
+    let mut spawn_index = 0usize;
+    for _ in 0..days {
+        advance(&mut state, &mut spawn_index);
+    }
+    state.iter().sum()
+}
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    type Model = [u64; 9];
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        let mut state = [0; 9];
+        for x in input.split(',') {
+            let v = x
+                .trim()
+                .parse::<usize>()
+                .map_err(|e| Error::parse(Self::DAY, e))?;
+            *state
+                .get_mut(v)
+                .ok_or_else(|| Error::parse(Self::DAY, format!("timer out of range: {}", v)))? += 1;
+        }
+        Ok(state)
+    }
+
+    fn part1(state: &Self::Model) -> Result<Answer, Error> {
+        Ok(simulate(state, 80).into())
+    }
+
+    fn part2(state: &Self::Model) -> Result<Answer, Error> {
+        Ok(simulate(state, 256).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "3,4,3,1,2";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day6>(SAMPLE, 5934, 26984457539u64);
+    }
+
+    #[test]
+    fn counts_fish_after_18_days() {
+        let state = Day6::parse(SAMPLE).unwrap();
+        assert_eq!(simulate(&state, 18), 26);
+    }
+}
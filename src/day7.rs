@@ -0,0 +1,66 @@
+use crate::solution::{Answer, Solution};
+use crate::{intcode, Error};
+
+pub struct Day7;
+
+fn int_sum(n: i32) -> i32 {
+    ((n + 1) * n) / 2
+}
+
+/// The input is also an intcode program, this returns what it prints.
+pub fn easter_egg(data: &[i32]) -> Result<String, Error> {
+    let mut m: intcode::Machine = data.iter().map(|x| *x as intcode::Int).collect();
+    let (_, output) = m.run(None)?;
+    Ok(output
+        .iter()
+        .map(|x| char::from_u32(*x as u32).unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect())
+}
+
+fn min_cost(data: &[i32], cost: impl Fn(i32) -> i32) -> Result<Answer, Error> {
+    let min = *data.iter().min().unwrap_or(&0);
+    let max = *data.iter().max().unwrap_or(&0);
+    (min..=max)
+        .map(|n| data.iter().map(|x| cost((x - n).abs())).sum::<i32>())
+        .min()
+        .map(Answer::from)
+        .ok_or_else(|| Error::solve(Day7::DAY, "no positions"))
+}
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+    type Model = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        input
+            .split(',')
+            .map(|x| x.trim().parse().map_err(|e| Error::parse(Self::DAY, e)))
+            .collect()
+    }
+
+    fn part1(data: &Self::Model) -> Result<Answer, Error> {
+        min_cost(data, |d| d)
+    }
+
+    fn part2(data: &Self::Model) -> Result<Answer, Error> {
+        min_cost(data, int_sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day7>(SAMPLE, 37, 168);
+    }
+
+    #[test]
+    fn prints_easter_egg() {
+        assert_eq!(easter_egg(&[104, 72, 104, 105, 99]).unwrap(), "Hi");
+    }
+}
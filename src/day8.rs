@@ -0,0 +1,150 @@
+use crate::solution::{Answer, Solution};
+use crate::Error;
+use std::collections::HashSet;
+
+pub struct Day8;
+
+pub struct SegmentedDisplay {
+    pattern: Vec<HashSet<char>>,
+    output: Vec<HashSet<char>>,
+}
+
+impl std::str::FromStr for SegmentedDisplay {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pattern, output) = s
+            .split_once('|')
+            .ok_or_else(|| Error::parse(Day8::DAY, "missing separator"))?;
+        let pattern = pattern
+            .split_whitespace()
+            .map(|s| s.chars().collect())
+            .collect();
+        let output = output
+            .split_whitespace()
+            .map(|s| s.chars().collect())
+            .collect();
+
+        Ok(Self { pattern, output })
+    }
+}
+
+impl SegmentedDisplay {
+    fn count_simple(&self) -> usize {
+        self.output
+            .iter()
+            .map(HashSet::len)
+            .filter(|x| *x == 2 || *x == 4 || *x == 3 || *x == 7)
+            .count()
+    }
+
+    fn decode(&self) -> Result<u32, Error> {
+        let mut number_map: [HashSet<char>; 10] = Default::default();
+        // unique count of segments
+        for p in &self.pattern {
+            let idx = match p.len() {
+                2 => 1,
+                3 => 7,
+                4 => 4,
+                7 => 8,
+                _ => continue,
+            };
+            number_map[idx] = p.clone();
+        }
+
+        for p in &self.pattern {
+            let idx = match p.len() {
+                2 | 3 | 4 | 7 => continue,
+                5 => {
+                    if p.is_superset(&number_map[1]) {
+                        3
+                    } else if number_map[4]
+                        .difference(&number_map[1])
+                        .all(|x| p.contains(x))
+                    {
+                        5
+                    } else {
+                        2
+                    }
+                }
+                6 => {
+                    if p.is_superset(&number_map[4]) {
+                        9
+                    } else if p.is_superset(&number_map[1]) {
+                        0
+                    } else {
+                        6
+                    }
+                }
+                l => return Err(Error::solve(Day8::DAY, format!("invalid length: {}", l))),
+            };
+            number_map[idx] = p.clone();
+        }
+        let mut res = 0;
+        for o in &self.output {
+            for (v, p) in number_map.iter().enumerate() {
+                if o == p {
+                    res = 10 * res + v;
+                }
+            }
+        }
+        Ok(res as u32)
+    }
+}
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    type Model = Vec<SegmentedDisplay>;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        input.lines().map(str::parse).collect()
+    }
+
+    fn part1(displays: &Self::Model) -> Result<Answer, Error> {
+        Ok(displays
+            .iter()
+            .map(SegmentedDisplay::count_simple)
+            .sum::<usize>()
+            .into())
+    }
+
+    fn part2(displays: &Self::Model) -> Result<Answer, Error> {
+        let mut sum = 0;
+        for d in displays {
+            sum += d.decode()?;
+        }
+        Ok(sum.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str =
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day8>(SAMPLE, 26, 61229);
+    }
+
+    #[test]
+    fn decodes_single_display() {
+        let display: SegmentedDisplay =
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf"
+                .parse()
+                .unwrap();
+        assert_eq!(display.decode().unwrap(), 5353);
+    }
+}
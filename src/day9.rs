@@ -0,0 +1,74 @@
+use crate::grid::{Grid, Pos};
+use crate::solution::{Answer, Solution};
+use crate::Error;
+
+pub struct Day9;
+
+fn get_risk(heightmap: &Grid<u32>, pos: Pos) -> Option<u32> {
+    let height = heightmap[pos];
+    if heightmap.neighbors4(pos).all(|n| heightmap[n] > height) {
+        Some(height + 1)
+    } else {
+        None
+    }
+}
+
+fn get_basin_size(heightmap: &mut Grid<u32>, pos: Pos) -> u32 {
+    if heightmap[pos] == 9 {
+        return 0;
+    }
+    heightmap[pos] = 9;
+    let neighbors: Vec<Pos> = heightmap.neighbors4(pos).collect();
+    1 + neighbors
+        .into_iter()
+        .map(|n| get_basin_size(heightmap, n))
+        .sum::<u32>()
+}
+
+fn low_points(heightmap: &Grid<u32>) -> impl Iterator<Item = (Pos, u32)> + '_ {
+    heightmap
+        .positions()
+        .filter_map(move |pos| get_risk(heightmap, pos).map(|risk| (pos, risk)))
+}
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+    type Model = Grid<u32>;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        Grid::parse(input, |c| c.to_digit(10)).map_err(|e| Error::parse(Self::DAY, e))
+    }
+
+    fn part1(heightmap: &Self::Model) -> Result<Answer, Error> {
+        Ok(low_points(heightmap)
+            .map(|(_, risk)| risk)
+            .sum::<u32>()
+            .into())
+    }
+
+    fn part2(heightmap: &Self::Model) -> Result<Answer, Error> {
+        let mut basinmap = heightmap.clone();
+        let mut basins: Vec<u32> = low_points(heightmap)
+            .map(|(pos, _)| get_basin_size(&mut basinmap, pos))
+            .collect();
+        basins.sort();
+        Ok(basins.iter().rev().take(3).product::<u32>().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::assert_answers;
+
+    const SAMPLE: &str = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+    #[test]
+    fn solves_sample() {
+        assert_answers::<Day9>(SAMPLE, 15, 1134);
+    }
+}
@@ -0,0 +1,315 @@
+/// Position in a grid as `(x, y)`, with `(0, 0)` in the top left corner.
+pub type Pos = (usize, usize);
+
+/// Offsets of the four orthogonal neighbors.
+pub const NEIGHBORS4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+/// Offsets of all eight neighbors, including the diagonal ones.
+pub const NEIGHBORS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid stored row by row.
+///
+/// Neighbors and offsets are bounds-checked, or wrap around at the edges if
+/// the grid was made [`wrapping`](Grid::wrapping).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    wrap: bool,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    Empty,
+    /// The contained line has a different length than the first one.
+    Ragged(usize),
+    InvalidChar {
+        c: char,
+        pos: Pos,
+    },
+}
+
+impl std::fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty grid"),
+            Self::Ragged(y) => write!(f, "line {} has a different length", y + 1),
+            Self::InvalidChar { c, pos } => {
+                write!(f, "invalid character '{}' at {},{}", c, pos.0, pos.1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl<T> Grid<T> {
+    /// Builds a grid by calling `f` for every position.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            wrap: false,
+            cells,
+        }
+    }
+
+    /// Parses a character map with one line per row. Every character is a
+    /// cell, including whitespace, and all lines must have the same length.
+    pub fn parse(s: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, ParseGridError> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        for l in s.lines() {
+            let len = cells.len();
+            for (x, c) in l.chars().enumerate() {
+                let pos = (x, height);
+                cells.push(f(c).ok_or(ParseGridError::InvalidChar { c, pos })?);
+            }
+            if height == 0 {
+                width = cells.len();
+            } else if cells.len() - len != width {
+                return Err(ParseGridError::Ragged(height));
+            }
+            height += 1;
+        }
+        if cells.is_empty() {
+            return Err(ParseGridError::Empty);
+        }
+        Ok(Self {
+            width,
+            height,
+            wrap: false,
+            cells,
+        })
+    }
+
+    /// Makes neighbors and offsets wrap around at the edges.
+    pub fn wrapping(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// The position `delta` away from `pos`, `None` if it is outside a
+    /// non-wrapping grid or the grid is empty.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        if self.cells.is_empty() {
+            None
+        } else if self.wrap {
+            let wrap =
+                |v: usize, d: isize, len: usize| (v as isize + d).rem_euclid(len as isize) as usize;
+            Some((wrap(x, dx, self.width), wrap(y, dy, self.height)))
+        } else {
+            let x = x.checked_add_signed(dx).filter(|x| *x < self.width)?;
+            let y = y.checked_add_signed(dy).filter(|y| *y < self.height)?;
+            Some((x, y))
+        }
+    }
+
+    /// The orthogonal neighbors of `pos`.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS4.iter().filter_map(move |d| self.offset(pos, *d))
+    }
+    /// All neighbors of `pos`, including the diagonal ones.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS8.iter().filter_map(move |d| self.offset(pos, *d))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            wrap: self.wrap,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draws the grid with one character per cell.
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        let mut res = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            res.extend(row.iter().map(&f));
+            res.push('\n');
+        }
+        res
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self::from_fn(width, height, |_| value.clone())
+    }
+}
+
+impl<T> std::ops::Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).expect("position outside of grid")
+    }
+}
+
+impl<T> std::ops::IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos).expect("position outside of grid")
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for c in row {
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456\n", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parses_and_renders() {
+        let grid = digits();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert_eq!(
+            grid.render(|d| if d % 2 == 0 { '#' } else { '.' }),
+            ".#.\n#.#\n"
+        );
+        assert_eq!(
+            Grid::parse("12\n3", |c| c.to_digit(10)),
+            Err(ParseGridError::Ragged(1))
+        );
+        assert_eq!(
+            Grid::parse("12\n\n34\n", |c| c.to_digit(10)),
+            Err(ParseGridError::Ragged(1))
+        );
+        assert_eq!(
+            Grid::parse("", |c| c.to_digit(10)),
+            Err(ParseGridError::Empty)
+        );
+        assert_eq!(
+            Grid::parse("\n\n", |c| c.to_digit(10)),
+            Err(ParseGridError::Empty)
+        );
+        assert_eq!(
+            Grid::parse(" 1\n 2", |c| c.to_digit(10)),
+            Err(ParseGridError::InvalidChar {
+                c: ' ',
+                pos: (0, 0)
+            })
+        );
+        let spaces = Grid::parse("# \n #\n", |c| Some(c == '#')).unwrap();
+        assert_eq!(spaces.render(|b| if *b { '#' } else { '.' }), "#.\n.#\n");
+        assert_eq!(
+            Grid::parse("1x", |c| c.to_digit(10)),
+            Err(ParseGridError::InvalidChar {
+                c: 'x',
+                pos: (1, 0)
+            })
+        );
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let grid = digits();
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 5);
+        let grid = grid.wrapping(true);
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (2, 0), (1, 0), (0, 1)]
+        );
+        assert_eq!(grid.offset((2, 1), (1, 1)), Some((0, 0)));
+        let empty = Grid::new(0, 3, 0).wrapping(true);
+        assert_eq!(empty.neighbors8((0, 0)).count(), 0);
+        assert_eq!(empty.offset((0, 1), (0, 1)), None);
+    }
+
+    #[test]
+    fn views_rows_and_columns() {
+        let grid = digits();
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        let sums: Vec<u32> = grid.columns().map(|c| c.sum()).collect();
+        assert_eq!(sums, [5, 7, 9]);
+    }
+}
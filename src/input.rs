@@ -1,8 +1,165 @@
-#[derive(Debug)]
 pub enum Error {
     Http(http_req::error::Error),
     Download(http_req::response::StatusCode),
     Io(ex::io::Error),
+    UnexpectedResponse(String),
+    /// The submitted answer already received this verdict.
+    KnownWrong(Verdict),
+    /// The part was already solved with the contained answer.
+    AlreadySolved(String),
+    /// The submitted answer lies outside the bounds implied by earlier verdicts.
+    OutOfBounds {
+        value: i64,
+        low: Option<i64>,
+        high: Option<i64>,
+    },
+    /// The input is not available locally and network access is disabled.
+    Offline {
+        year: u32,
+        day: u32,
+    },
+    /// No session token was found in any of the places listed in [`find_session`].
+    NoSession,
+    /// The session token from the contained source is malformed.
+    InvalidSession(String),
+    /// The server did not accept the session token.
+    SessionRejected,
+    /// The puzzle is not unlocked yet, the duration is the remaining time.
+    NotYetAvailable(std::time::Duration),
+    /// The input of a day could not be parsed.
+    Parse {
+        day: u32,
+        message: String,
+    },
+    /// The input file of a day could not be opened or read.
+    InputFile {
+        day: u32,
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    Intcode(crate::intcode::Error),
+    /// A solution failed on otherwise valid input.
+    Solve {
+        day: u32,
+        message: String,
+    },
+    /// There is no solution for the contained day.
+    NoSolution(u32),
+    /// A data file like a benchmark baseline is malformed.
+    InvalidFile {
+        path: std::path::PathBuf,
+        message: String,
+    },
+}
+
+impl Error {
+    pub fn parse(day: u32, message: impl std::fmt::Display) -> Self {
+        Self::Parse {
+            day,
+            message: message.to_string(),
+        }
+    }
+    pub fn solve(day: u32, message: impl std::fmt::Display) -> Self {
+        Self::Solve {
+            day,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Http(_) => write!(f, "HTTP request failed"),
+            Self::Download(status) => write!(f, "server replied with status {}", status),
+            Self::Io(_) => write!(f, "file system error"),
+            Self::UnexpectedResponse(_) => write!(f, "unexpected response from server"),
+            Self::KnownWrong(verdict) => {
+                write!(f, "answer was already submitted and is {}", verdict)
+            }
+            Self::AlreadySolved(answer) => {
+                write!(f, "part is already solved with answer {}", answer)
+            }
+            Self::OutOfBounds { value, low, high } => {
+                let bound = |b: &Option<i64>| b.map(|b| b.to_string()).unwrap_or_default();
+                write!(
+                    f,
+                    "answer {} is outside the known bounds {}..{}",
+                    value,
+                    bound(low),
+                    bound(high)
+                )
+            }
+            Self::Offline { year, day } => write!(
+                f,
+                "input of {} day {} is not cached and downloads are disabled",
+                year, day
+            ),
+            Self::NoSession => write!(
+                f,
+                "no session token found, set AOC_SESSION or create ~/.config/aoc/session or ./cookie"
+            ),
+            Self::InvalidSession(source) => write!(
+                f,
+                "session token from {} is malformed, it must contain only the token",
+                source
+            ),
+            Self::NotYetAvailable(wait) => {
+                let secs = wait.as_secs();
+                write!(
+                    f,
+                    "puzzle is not available yet, it unlocks in {}h {}m {}s",
+                    secs / 3600,
+                    secs / 60 % 60,
+                    secs % 60
+                )
+            }
+            Self::SessionRejected => {
+                write!(f, "the server rejected the session token, it may have expired")
+            }
+            Self::Parse { day, message } => {
+                write!(f, "failed to parse input of day {}: {}", day, message)
+            }
+            Self::InputFile { day, path, .. } => write!(
+                f,
+                "cannot read input of day {} from {}",
+                day,
+                path.display()
+            ),
+            Self::Intcode(_) => write!(f, "intcode machine failed"),
+            Self::Solve { day, message } => write!(f, "day {} failed: {}", day, message),
+            Self::NoSolution(day) => write!(f, "there is no solution for day {}", day),
+            Self::InvalidFile { path, message } => {
+                write!(f, "{} is invalid: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+// Debug is what `main() -> Result<(), Error>` prints, so it shows the message
+// followed by its causes instead of the raw structure.
+impl std::fmt::Debug for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)?;
+        let mut source = std::error::Error::source(self);
+        while let Some(s) = source {
+            write!(f, ": {}", s)?;
+            source = s.source();
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Http(e) => Some(e),
+            Self::Io(e) => Some(e),
+            Self::InputFile { source, .. } => Some(source),
+            Self::Intcode(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 impl From<http_req::error::Error> for Error {
@@ -20,36 +177,727 @@ impl From<http_req::response::StatusCode> for Error {
         Self::Download(e)
     }
 }
-fn generate_path(day: u32) -> std::path::PathBuf {
+impl From<crate::intcode::Error> for Error {
+    fn from(e: crate::intcode::Error) -> Self {
+        Self::Intcode(e)
+    }
+}
+
+/// The event year used by [`get_input`].
+pub const DEFAULT_YEAR: u32 = 2021;
+
+/// Server reply to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// An answer was submitted too recently, the duration is the remaining wait.
+    RateLimited(std::time::Duration),
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "too high"),
+            Self::TooLow => write!(f, "too low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::RateLimited(wait) => write!(f, "rate limited for {}s", wait.as_secs()),
+        }
+    }
+}
+
+pub(crate) fn generate_path(year: u32, day: u32) -> std::path::PathBuf {
+    ["input", &year.to_string(), &day.to_string()]
+        .iter()
+        .collect()
+}
+/// Path used by earlier versions, which only knew about 2021.
+fn legacy_path(day: u32) -> std::path::PathBuf {
     ["input", &day.to_string()].iter().collect()
 }
-fn download_input(day: u32) -> Result<(), Error> {
-    let session = ex::fs::read_to_string("cookie")?;
-    let cookie = format!("session={}", session);
-    let url = format!("https://adventofcode.com/{}/day/{}/input", 2021, day);
-    let url_str: &str = &url;
-    let uri = http_req::uri::Uri::try_from(url_str).unwrap();
-
-    let path = generate_path(day);
-    let mut file = ex::fs::File::create(&path)?;
-    let resp = http_req::request::Request::new(&uri)
-        .header("Cookie", &cookie)
-        .send(&mut file)?;
-    let status = resp.status_code();
+/// Moves a cache file from the flat `input/<day>` layout to `input/2021/<day>`.
+fn migrate_legacy(year: u32, day: u32) -> Result<bool, Error> {
+    let old = legacy_path(day);
+    if year != DEFAULT_YEAR || !old.is_file() {
+        return Ok(false);
+    }
+    let new = generate_path(year, day);
+    if let Some(dir) = new.parent() {
+        ex::fs::create_dir_all(dir)?;
+    }
+    ex::fs::rename(old, &new)?;
+    // There is nothing to check the old file against, so it is trusted once.
+    let data = ex::fs::read(&new)?;
+    write_manifest(&new, &Manifest::of(&data))?;
+    Ok(true)
+}
+
+/// Size and checksum of a cached input, stored as `<day>.sum` next to it.
+#[derive(Debug, PartialEq, Eq)]
+struct Manifest {
+    size: u64,
+    checksum: u64,
+}
+
+/// 64 bit FNV-1a, enough to notice truncated or garbled files.
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+impl Manifest {
+    fn of(data: &[u8]) -> Self {
+        Self {
+            size: data.len() as u64,
+            checksum: fnv1a(data),
+        }
+    }
+}
+
+impl std::str::FromStr for Manifest {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut size = None;
+        let mut checksum = None;
+        for l in s.lines() {
+            match l.split_once(' ').ok_or(())? {
+                ("size", v) => size = Some(v.parse().map_err(|_| ())?),
+                ("fnv1a64", v) => checksum = Some(u64::from_str_radix(v, 16).map_err(|_| ())?),
+                _ => return Err(()),
+            }
+        }
+        Ok(Self {
+            size: size.ok_or(())?,
+            checksum: checksum.ok_or(())?,
+        })
+    }
+}
+
+impl std::fmt::Display for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "size {}", self.size)?;
+        writeln!(f, "fnv1a64 {:016x}", self.checksum)
+    }
+}
+
+/// Appends `suffix` to the file name, `input/2021/1` becomes `input/2021/1.<suffix>`.
+fn sibling_path(path: &std::path::Path, suffix: &str) -> std::path::PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    name.into()
+}
+fn manifest_path(path: &std::path::Path) -> std::path::PathBuf {
+    sibling_path(path, "sum")
+}
+fn temp_path(path: &std::path::Path) -> std::path::PathBuf {
+    sibling_path(path, &format!("{}.tmp", std::process::id()))
+}
+/// Writes `data` next to `path` and renames it into place, so readers never see
+/// a partially written file.
+/// The temporary file is removed again if writing or renaming fails.
+fn write_atomic(path: &std::path::Path, data: &[u8]) -> Result<(), Error> {
+    let tmp = temp_path(path);
+    let res = ex::fs::write(&tmp, data).and_then(|_| ex::fs::rename(&tmp, path));
+    if res.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    Ok(res?)
+}
+/// Removes the temporary files of `path` and its manifest that
+/// [`write_atomic`] left behind in any process, e.g. one that was killed while
+/// downloading.
+fn remove_stale_temps(path: &std::path::Path) {
+    let Some(dir) = path.parent().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return;
+    };
+    let stale = |name: &str| {
+        [path, &manifest_path(path)].iter().any(|p| {
+            let prefix = format!("{}.", p.file_name().unwrap_or_default().to_string_lossy());
+            name.strip_prefix(&prefix)
+                .and_then(|rest| rest.strip_suffix(".tmp"))
+                .is_some_and(|pid| !pid.is_empty() && pid.bytes().all(|b| b.is_ascii_digit()))
+        })
+    };
+    for entry in dir.flatten() {
+        if stale(&entry.file_name().to_string_lossy()) {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+fn write_manifest(path: &std::path::Path, manifest: &Manifest) -> Result<(), Error> {
+    write_atomic(&manifest_path(path), manifest.to_string().as_bytes())
+}
+/// Checks a cached input against its manifest. Missing files, missing manifests
+/// and mismatches all count as invalid.
+fn is_valid(path: &std::path::Path) -> bool {
+    let manifest = ex::fs::read_to_string(manifest_path(path))
+        .ok()
+        .and_then(|m| m.parse::<Manifest>().ok());
+    match (manifest, ex::fs::read(path)) {
+        (Some(manifest), Ok(data)) => manifest == Manifest::of(&data),
+        _ => false,
+    }
+}
+/// Writes the manifest of a cache file that has none, e.g. one written before
+/// manifests existed, so it is trusted once instead of downloaded again.
+/// Only files that look like a complete input, not empty and ending with a
+/// newline, are adopted. Returns whether there was such a file.
+fn adopt_unverified(path: &std::path::Path) -> Result<bool, Error> {
+    if !path.is_file() || manifest_path(path).exists() {
+        return Ok(false);
+    }
+    let data = ex::fs::read(path)?;
+    if !data.ends_with(b"\n") {
+        return Ok(false);
+    }
+    write_manifest(path, &Manifest::of(&data))?;
+    Ok(true)
+}
+/// Returns the contents of a cache file if it matches its manifest.
+pub(crate) fn read_cached(path: &std::path::Path) -> Option<Vec<u8>> {
+    if is_valid(path) {
+        ex::fs::read(path).ok()
+    } else {
+        None
+    }
+}
+/// Atomically stores `data` in the cache file `path` together with its manifest.
+pub(crate) fn store_cached(path: &std::path::Path, data: &[u8]) -> Result<(), Error> {
+    if let Some(dir) = path.parent() {
+        ex::fs::create_dir_all(dir)?;
+    }
+    write_atomic(path, data)?;
+    write_manifest(path, &Manifest::of(data))
+}
+fn session_config_path() -> Option<std::path::PathBuf> {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(std::path::PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| std::path::Path::new(&h).join(".config")))?;
+    Some(config.join("aoc").join("session"))
+}
+/// Looks up the session token, trying in order:
+///
+/// * the `AOC_SESSION` environment variable
+/// * `$XDG_CONFIG_HOME/aoc/session`, defaulting to `~/.config/aoc/session`
+/// * the file `cookie` in the current directory
+///
+/// Returns the token and a description of where it was found.
+pub fn find_session() -> Result<(String, String), Error> {
+    let files = session_config_path()
+        .into_iter()
+        .chain(Some(std::path::PathBuf::from("cookie")));
+    find_session_in(std::env::var_os("AOC_SESSION"), files)
+}
+/// [`find_session`] with the value of `AOC_SESSION` and the files to try in
+/// order. The first non-empty source wins and later files are not read.
+fn find_session_in(
+    env: Option<std::ffi::OsString>,
+    files: impl IntoIterator<Item = std::path::PathBuf>,
+) -> Result<(String, String), Error> {
+    let mut found = env
+        .map(|token| {
+            (
+                token.to_string_lossy().into_owned(),
+                "AOC_SESSION".to_string(),
+            )
+        })
+        .filter(|(t, _)| !t.trim().is_empty());
+    for path in files {
+        if found.is_some() {
+            break;
+        }
+        if path.is_file() {
+            found = Some((ex::fs::read_to_string(&path)?, path.display().to_string()))
+                .filter(|(t, _)| !t.trim().is_empty());
+        }
+    }
+    let (token, source) = found.ok_or(Error::NoSession)?;
+    let token = token.trim();
+    // Tokens are hex strings; a leftover `session=` prefix is the most common mistake.
+    if !token.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(Error::InvalidSession(source));
+    }
+    Ok((token.to_string(), source))
+}
+fn session_cookie() -> Result<String, Error> {
+    let (session, _) = find_session()?;
+    Ok(format!("session={}", session))
+}
+/// Maps a failed request for a puzzle to an error, singling out a rejected
+/// session and puzzles that are not unlocked yet.
+fn status_error(status: http_req::response::StatusCode, year: u32, day: u32) -> Error {
+    match u16::from(status) {
+        400 => Error::SessionRejected,
+        404 => match crate::client::unlock_time(year, day)
+            .duration_since(std::time::SystemTime::now())
+        {
+            Ok(wait) => Error::NotYetAvailable(wait),
+            Err(_) => Error::Download(status),
+        },
+        _ => Error::Download(status),
+    }
+}
+/// Fetches `<year>/day/<day><suffix>` with the session cookie.
+pub(crate) fn http_get(year: u32, day: u32, suffix: &str) -> Result<Vec<u8>, Error> {
+    let cookie = session_cookie()?;
+    let path = format!("{}/day/{}{}", year, day, suffix);
+    let (status, body) = crate::client::Client::from_env().send(&path, &cookie, None)?;
     if !status.is_success() {
-        drop(file);
-        ex::fs::remove_file(&path)?;
-        return Err(Error::Download(status));
+        return Err(status_error(status, year, day));
+    }
+    Ok(body)
+}
+fn download_input(year: u32, day: u32) -> Result<(), Error> {
+    let path = generate_path(year, day);
+    let res = http_get(year, day, "/input").and_then(|data| store_cached(&path, &data));
+    if res.is_err() {
+        remove_stale_temps(&path);
+    }
+    res
+}
+/// Where [`get_input`] reads a day's input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// Reads this file, whatever day is requested.
+    Path(std::path::PathBuf),
+    /// Reads standard input.
+    Stdin,
+    /// Reads `<dir>/<day>`, never touching the network.
+    Dir(std::path::PathBuf),
+    /// Reads the `input/<year>/<day>` cache. Missing or corrupt entries are
+    /// downloaded, or reported as [`Error::Offline`] if `offline` is set.
+    Cache { offline: bool },
+}
+
+impl InputSource {
+    /// Picks the source from the environment:
+    ///
+    /// * `AOC_INPUT`: a file to read, or `-` for stdin
+    /// * `AOC_INPUT_DIR`: a directory containing one file per day
+    /// * `AOC_OFFLINE`: if set to anything but `0`, only the cache is used
+    ///
+    /// Without any of these, the cache is used and filled from the network.
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var_os(name))
+    }
+
+    /// Like [`from_env`](Self::from_env), with the variables looked up by `lookup`.
+    pub fn from_vars(lookup: impl Fn(&str) -> Option<std::ffi::OsString>) -> Self {
+        let var = |name| lookup(name).filter(|v| !v.is_empty());
+        if let Some(input) = var("AOC_INPUT") {
+            if input == "-" {
+                Self::Stdin
+            } else {
+                Self::Path(input.into())
+            }
+        } else if let Some(dir) = var("AOC_INPUT_DIR") {
+            Self::Dir(dir.into())
+        } else {
+            let offline = var("AOC_OFFLINE").map(|v| v != "0").unwrap_or(false);
+            Self::Cache { offline }
+        }
     }
-    Ok(())
+
+    pub fn open(&self, year: u32, day: u32) -> Result<Input, Error> {
+        Ok(match self {
+            Self::Path(path) => Input::File(open_input(day, path)?),
+            Self::Stdin => Input::Stdin(std::io::stdin()),
+            Self::Dir(dir) => Input::File(open_input(day, &dir.join(day.to_string()))?),
+            Self::Cache { offline } => {
+                let path = generate_path(year, day);
+                if !is_valid(&path) && !adopt_unverified(&path)? && !migrate_legacy(year, day)? {
+                    if *offline {
+                        return Err(Error::Offline { year, day });
+                    }
+                    download_input(year, day)?;
+                }
+                Input::File(open_input(day, &path)?)
+            }
+        })
+    }
+
+    fn path(&self, year: u32, day: u32) -> std::path::PathBuf {
+        match self {
+            Self::Path(path) => path.clone(),
+            Self::Stdin => "-".into(),
+            Self::Dir(dir) => dir.join(day.to_string()),
+            Self::Cache { .. } => generate_path(year, day),
+        }
+    }
+
+    /// Reads the whole input of the given day.
+    pub fn read_to_string(&self, year: u32, day: u32) -> Result<String, Error> {
+        let mut res = String::new();
+        std::io::Read::read_to_string(&mut self.open(year, day)?, &mut res).map_err(|source| {
+            Error::InputFile {
+                day,
+                path: self.path(year, day),
+                source,
+            }
+        })?;
+        Ok(res)
+    }
+}
+
+fn open_input(day: u32, path: &std::path::Path) -> Result<ex::fs::File, Error> {
+    ex::fs::File::open(path).map_err(|e| Error::InputFile {
+        day,
+        path: path.to_path_buf(),
+        source: ex::Wrapper::into_inner(e),
+    })
 }
-pub fn get_input(day: u32) -> Result<ex::fs::File, Error> {
-    let path = generate_path(day);
-    let file_in = ex::fs::File::open(&path);
-    if file_in.is_ok() {
-        file_in.map_err(|e| e.into())
+
+/// An opened puzzle input.
+pub enum Input {
+    File(ex::fs::File),
+    Stdin(std::io::Stdin),
+}
+
+impl std::io::Read for Input {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::File(f) => f.read(buf),
+            Self::Stdin(s) => s.read(buf),
+        }
+    }
+}
+
+/// Opens the input of the given day of `year` from the source selected by
+/// [`InputSource::from_env`].
+pub fn get_input_for(year: u32, day: u32) -> Result<Input, Error> {
+    InputSource::from_env().open(year, day)
+}
+/// Opens the input of the given day of 2021.
+pub fn get_input(day: u32) -> Result<Input, Error> {
+    get_input_for(DEFAULT_YEAR, day)
+}
+
+fn url_encode(s: &str) -> String {
+    let mut res = String::new();
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || b"-_.~".contains(&b) {
+            res.push(b as char);
+        } else {
+            res.push_str(&format!("%{:02X}", b));
+        }
+    }
+    res
+}
+/// Parses a wait time like `1m 5s` as it appears in the rate limit message.
+fn parse_wait(text: &str) -> Option<std::time::Duration> {
+    let lower = text.to_lowercase();
+    let end = lower.find(" left to wait")?;
+    let start = lower[..end].rfind("you have ")? + "you have ".len();
+    let mut secs = 0;
+    for part in lower[start..end].split_whitespace() {
+        let (i, unit) = part.char_indices().last()?;
+        let num: u64 = part.get(..i)?.parse().ok()?;
+        secs += match unit {
+            'h' => num * 3600,
+            'm' => num * 60,
+            's' => num,
+            _ => return None,
+        };
+    }
+    Some(std::time::Duration::from_secs(secs))
+}
+fn parse_verdict(html: &str) -> Option<Verdict> {
+    let text = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    Some(if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("too recently") {
+        Verdict::RateLimited(parse_wait(text)?)
+    } else if text.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if text.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if text.contains("That's not the right answer") {
+        Verdict::Wrong
     } else {
-        download_input(day)?;
-        ex::fs::File::open(path).map_err(|e| e.into())
+        return None;
+    })
+}
+fn post_answer(
+    client: &crate::client::Client,
+    cookie: &str,
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<Verdict, Error> {
+    let path = format!("{}/day/{}/answer", year, day);
+    let form = format!("level={}&answer={}", part, url_encode(answer));
+    let (status, response) = client.send(&path, cookie, Some(&form))?;
+    if !status.is_success() {
+        return Err(status_error(status, year, day));
+    }
+    let html = String::from_utf8_lossy(&response);
+    parse_verdict(&html).ok_or_else(|| Error::UnexpectedResponse(html.into_owned()))
+}
+/// Submits `answer` for `part` (1 or 2) of the given day and returns the server's verdict.
+///
+/// Verdicts are recorded in the day's [`Ledger`](crate::ledger::Ledger). Answers that
+/// are known to be wrong are rejected without contacting the server.
+pub fn submit_answer(
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: impl std::fmt::Display,
+) -> Result<Verdict, Error> {
+    let answer = answer.to_string();
+    let mut ledger = crate::ledger::Ledger::open(year, day)?;
+    if let Some(verdict) = ledger.check(part, &answer)? {
+        return Ok(verdict);
+    }
+    let cookie = session_cookie()?;
+    let client = crate::client::Client::from_env();
+    let verdict = post_answer(&client, &cookie, year, day, part, &answer)?;
+    ledger.record(part, &answer, Some(verdict.clone()))?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::serve;
+
+    fn test_client(base_url: &str) -> crate::client::Client {
+        crate::client::Client::default()
+            .with_base_url(base_url)
+            .with_state_path(None)
+    }
+
+    fn submit_to(body: &'static str, answer: &str) -> (Verdict, String) {
+        let (base_url, handle) = serve(vec![(200, body)]);
+        let verdict =
+            post_answer(&test_client(&base_url), "session=abc", 2021, 7, 2, answer).unwrap();
+        (verdict, handle.join().unwrap().remove(0))
+    }
+
+    #[test]
+    fn detects_corrupt_cache() {
+        let path = std::env::temp_dir().join(format!("aoc2021-cache-{}", std::process::id()));
+        assert!(!is_valid(&path));
+        write_atomic(&path, b"1\n2\n3\n").unwrap();
+        assert!(!is_valid(&path));
+        write_manifest(&path, &Manifest::of(b"1\n2\n3\n")).unwrap();
+        assert!(is_valid(&path));
+        std::fs::write(&path, b"1\n2\n").unwrap();
+        assert!(!is_valid(&path));
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(manifest_path(&path)).unwrap();
+    }
+
+    #[test]
+    fn adopts_cache_without_manifest() {
+        let path = std::env::temp_dir().join(format!("aoc2021-adopt-{}", std::process::id()));
+        assert!(!adopt_unverified(&path).unwrap());
+        std::fs::write(&path, b"1\n2\n").unwrap();
+        assert!(adopt_unverified(&path).unwrap());
+        assert!(is_valid(&path));
+        // A file that does not match its manifest is corrupt, not unverified.
+        std::fs::write(&path, b"1\n").unwrap();
+        assert!(!adopt_unverified(&path).unwrap());
+        assert!(!is_valid(&path));
+        std::fs::remove_file(manifest_path(&path)).unwrap();
+        // Empty and truncated files are downloaded again instead.
+        for data in [&b""[..], b"1\n2"] {
+            std::fs::write(&path, data).unwrap();
+            assert!(!adopt_unverified(&path).unwrap());
+            assert!(!is_valid(&path));
+            assert!(!manifest_path(&path).exists());
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn removes_stale_temp_files() {
+        let dir = std::env::temp_dir().join(format!("aoc2021-temps-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("1");
+        let stale = [dir.join("1.123.tmp"), dir.join("1.sum.456.tmp")];
+        let kept = [dir.join("11.123.tmp"), dir.join("1.x.tmp"), dir.join("1")];
+        for file in stale.iter().chain(&kept) {
+            std::fs::write(file, b"").unwrap();
+        }
+        remove_stale_temps(&path);
+        assert!(stale.iter().all(|file| !file.exists()));
+        assert!(kept.iter().all(|file| file.exists()));
+        // A failed write leaves nothing behind.
+        assert!(write_atomic(&dir.join("missing").join("2"), b"2\n").is_err());
+        assert!(write_atomic(&dir, b"").is_err());
+        assert!(!temp_path(&dir).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn source(vars: &[(&str, &str)]) -> InputSource {
+        InputSource::from_vars(|name| vars.iter().find(|(n, _)| *n == name).map(|(_, v)| v.into()))
+    }
+
+    #[test]
+    fn selects_input_source() {
+        assert_eq!(source(&[]), InputSource::Cache { offline: false });
+        assert_eq!(source(&[("AOC_INPUT", "-")]), InputSource::Stdin);
+        assert_eq!(
+            source(&[("AOC_INPUT", "day1.txt")]),
+            InputSource::Path("day1.txt".into())
+        );
+        assert_eq!(
+            source(&[("AOC_INPUT_DIR", "samples")]),
+            InputSource::Dir("samples".into())
+        );
+        assert_eq!(
+            source(&[("AOC_OFFLINE", "1")]),
+            InputSource::Cache { offline: true }
+        );
+        assert_eq!(
+            source(&[("AOC_OFFLINE", "0")]),
+            InputSource::Cache { offline: false }
+        );
+        // AOC_INPUT wins over AOC_INPUT_DIR, which wins over AOC_OFFLINE, and
+        // empty variables count as unset.
+        let all = [
+            ("AOC_INPUT", "day1.txt"),
+            ("AOC_INPUT_DIR", "samples"),
+            ("AOC_OFFLINE", "1"),
+        ];
+        assert_eq!(source(&all), InputSource::Path("day1.txt".into()));
+        assert_eq!(source(&all[1..]), InputSource::Dir("samples".into()));
+        assert_eq!(
+            source(&[("AOC_INPUT", ""), ("AOC_INPUT_DIR", "samples")]),
+            InputSource::Dir("samples".into())
+        );
+    }
+
+    #[test]
+    fn offline_cache_miss() {
+        let res = source(&[("AOC_OFFLINE", "yes")]).open(2000, 26);
+        assert!(matches!(
+            res,
+            Err(Error::Offline {
+                year: 2000,
+                day: 26
+            })
+        ));
+    }
+
+    #[test]
+    fn finds_session_in_priority_order() {
+        let dir = std::env::temp_dir().join(format!("aoc2021-session-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (config, cookie, blank) = (dir.join("session"), dir.join("cookie"), dir.join("blank"));
+        std::fs::write(&config, "c0ffee\n").unwrap();
+        std::fs::write(&cookie, "beef").unwrap();
+        std::fs::write(&blank, " \n").unwrap();
+        let find = |env: Option<&str>, files: &[&std::path::PathBuf]| {
+            find_session_in(env.map(Into::into), files.iter().map(|p| p.to_path_buf()))
+        };
+        let (token, source) = find(Some("abc123"), &[&config, &cookie]).unwrap();
+        assert_eq!((token.as_str(), source.as_str()), ("abc123", "AOC_SESSION"));
+        let (token, source) = find(Some(""), &[&blank, &config, &cookie]).unwrap();
+        assert_eq!(
+            (token, source),
+            ("c0ffee".to_string(), config.display().to_string())
+        );
+        let missing = dir.join("missing");
+        let (token, _) = find(None, &[&missing, &cookie]).unwrap();
+        assert_eq!(token, "beef");
+        assert!(matches!(
+            find(None, &[&missing, &blank]),
+            Err(Error::NoSession)
+        ));
+        std::fs::write(&cookie, "session=beef").unwrap();
+        assert!(matches!(
+            find(None, &[&cookie]),
+            Err(Error::InvalidSession(_))
+        ));
+        // Sources after the first hit are not read at all.
+        std::fs::write(&cookie, [0xff, 0xfe]).unwrap();
+        assert!(find(None, &[&config, &cookie]).is_ok());
+        assert!(find(None, &[&cookie]).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn locked_puzzle() {
+        let (base_url, handle) = serve(vec![(404, "")]);
+        let res = post_answer(&test_client(&base_url), "session=abc", 2999, 1, 1, "1");
+        handle.join().unwrap();
+        assert!(matches!(res, Err(Error::NotYetAvailable(_))));
+    }
+
+    #[test]
+    fn posts_answer_form() {
+        let (verdict, request) = submit_to(
+            "<main><article><p>That's the right answer! You are one gold star closer.</p></article></main>",
+            "12 AB",
+        );
+        assert_eq!(verdict, Verdict::Correct);
+        assert!(request.starts_with("POST /2021/day/7/answer "));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+        assert!(request.ends_with("level=2&answer=12%20AB"));
+    }
+
+    #[test]
+    fn parses_wrong_answers() {
+        let (verdict, _) = submit_to(
+            "<article><p>That's not the right answer; your answer is too high.</p></article>",
+            "1",
+        );
+        assert_eq!(verdict, Verdict::TooHigh);
+        let (verdict, _) = submit_to(
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+            "1",
+        );
+        assert_eq!(verdict, Verdict::TooLow);
+        let (verdict, _) = submit_to(
+            "<article><p>That's not the right answer. If you're stuck, ...</p></article>",
+            "1",
+        );
+        assert_eq!(verdict, Verdict::Wrong);
+    }
+
+    #[test]
+    fn parses_rate_limit() {
+        let (verdict, _) = submit_to(
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. </p></article>",
+            "1",
+        );
+        assert_eq!(
+            verdict,
+            Verdict::RateLimited(std::time::Duration::from_secs(65))
+        );
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(
+            parse_wait("You have 1h 2m 3s left to wait."),
+            Some(std::time::Duration::from_secs(3723))
+        );
+        assert_eq!(parse_wait("you have 30é left to wait"), None);
+        assert_eq!(parse_wait("you have ü left to wait"), None);
+        assert_eq!(parse_wait("you have 5 minutes left to wait"), None);
+    }
+
+    #[test]
+    fn unknown_response() {
+        let (base_url, handle) = serve(vec![(
+            200,
+            "<article><p>You don't seem to be solving the right level.</p></article>",
+        )]);
+        let res = post_answer(&test_client(&base_url), "session=abc", 2021, 7, 1, "1");
+        handle.join().unwrap();
+        assert!(matches!(res, Err(Error::UnexpectedResponse(_))));
     }
 }
@@ -1,15 +1,134 @@
 use std::convert::TryFrom;
 use std::io::BufRead;
 
+pub mod asm;
+pub mod debug;
+pub mod disasm;
+pub mod profile;
+
 pub type Int = i64;
 
+/// An operation, the instruction without its parameter modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Mul,
+    In,
+    Out,
+    Jt,
+    Jf,
+    Lt,
+    Eq,
+    Arb,
+    Hlt,
+}
+
+impl Op {
+    pub const ALL: [Op; 10] = [
+        Op::Add,
+        Op::Mul,
+        Op::In,
+        Op::Out,
+        Op::Jt,
+        Op::Jf,
+        Op::Lt,
+        Op::Eq,
+        Op::Arb,
+        Op::Hlt,
+    ];
+
+    /// The opcode, i.e. the last two digits of the instruction.
+    pub fn code(self) -> Int {
+        match self {
+            Op::Add => 1,
+            Op::Mul => 2,
+            Op::In => 3,
+            Op::Out => 4,
+            Op::Jt => 5,
+            Op::Jf => 6,
+            Op::Lt => 7,
+            Op::Eq => 8,
+            Op::Arb => 9,
+            Op::Hlt => 99,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Mul => "mul",
+            Op::In => "in",
+            Op::Out => "out",
+            Op::Jt => "jt",
+            Op::Jf => "jf",
+            Op::Lt => "lt",
+            Op::Eq => "eq",
+            Op::Arb => "arb",
+            Op::Hlt => "hlt",
+        }
+    }
+
+    pub fn param_count(self) -> usize {
+        match self {
+            Op::Add | Op::Mul | Op::Lt | Op::Eq => 3,
+            Op::Jt | Op::Jf => 2,
+            Op::In | Op::Out | Op::Arb => 1,
+            Op::Hlt => 0,
+        }
+    }
+
+    /// Index of the parameter the instruction writes to.
+    pub fn dest(self) -> Option<usize> {
+        match self {
+            Op::Add | Op::Mul | Op::Lt | Op::Eq => Some(2),
+            Op::In => Some(0),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<Int> for Op {
+    type Error = Error;
+
+    fn try_from(value: Int) -> Result<Self, Self::Error> {
+        Op::ALL
+            .into_iter()
+            .find(|op| op.code() == value)
+            .ok_or(Error::IllegalOp(value, None))
+    }
+}
+
+impl std::fmt::Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.mnemonic())
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     IllegalOp(Int, Option<usize>),
     IllegalParamMode(Int, Option<usize>),
     TriedToWriteImmediate,
+    /// The instruction pointer left the memory, e.g. after a jump.
+    IpOutOfBounds(usize),
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let at = |loc: &Option<usize>| loc.map(|l| format!(" at {}", l)).unwrap_or_default();
+        match self {
+            Error::IllegalOp(v, loc) => write!(f, "illegal opcode {}{}", v, at(loc)),
+            Error::IllegalParamMode(v, loc) => {
+                write!(f, "illegal parameter mode {}{}", v, at(loc))
+            }
+            Error::TriedToWriteImmediate => write!(f, "tried to write to an immediate parameter"),
+            Error::IpOutOfBounds(ip) => write!(f, "instruction pointer {} is outside memory", ip),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 impl Error {
     pub fn add_loc(self, loc: usize) -> Self {
         match self {
@@ -19,7 +138,7 @@ impl Error {
         }
     }
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum ParamMode {
     Pos,
     Imm,
@@ -38,6 +157,17 @@ impl TryFrom<Int> for ParamMode {
         }
     }
 }
+
+impl ParamMode {
+    /// The parameter `value` in this mode as written in a listing.
+    fn operand(&self, value: Int) -> disasm::Operand {
+        match self {
+            ParamMode::Pos => disasm::Operand::Pos(value),
+            ParamMode::Imm => disasm::Operand::Imm(value),
+            ParamMode::Rel => disasm::Operand::Rel(value),
+        }
+    }
+}
 #[derive(Debug)]
 enum Opcode {
     Add(ParamMode, ParamMode, ParamMode),
@@ -59,27 +189,107 @@ impl TryFrom<Int> for Opcode {
         let p1 = ParamMode::try_from((value / 100) % 10)?;
         let p2 = ParamMode::try_from((value / 1000) % 10)?;
         let p3 = ParamMode::try_from((value / 10000) % 10)?;
-        match value % 100 {
-            1 => Ok(Opcode::Add(p1, p2, p3)),
-            2 => Ok(Opcode::Mul(p1, p2, p3)),
-            3 => Ok(Opcode::Input(p1)),
-            4 => Ok(Opcode::Output(p1)),
-            5 => Ok(Opcode::Jit(p1, p2)),
-            6 => Ok(Opcode::Jif(p1, p2)),
-            7 => Ok(Opcode::Lt(p1, p2, p3)),
-            8 => Ok(Opcode::Eq(p1, p2, p3)),
-            9 => Ok(Opcode::Arb(p1)),
-            99 => Ok(Opcode::Halt),
-            x => Err(Error::IllegalOp(x, None)),
+        Ok(match Op::try_from(value % 100)? {
+            Op::Add => Opcode::Add(p1, p2, p3),
+            Op::Mul => Opcode::Mul(p1, p2, p3),
+            Op::In => Opcode::Input(p1),
+            Op::Out => Opcode::Output(p1),
+            Op::Jt => Opcode::Jit(p1, p2),
+            Op::Jf => Opcode::Jif(p1, p2),
+            Op::Lt => Opcode::Lt(p1, p2, p3),
+            Op::Eq => Opcode::Eq(p1, p2, p3),
+            Op::Arb => Opcode::Arb(p1),
+            Op::Hlt => Opcode::Halt,
+        })
+    }
+}
+
+impl Opcode {
+    /// The operation and the modes of its parameters.
+    fn into_parts(self) -> (Op, Vec<ParamMode>) {
+        match self {
+            Opcode::Add(p1, p2, p3) => (Op::Add, vec![p1, p2, p3]),
+            Opcode::Mul(p1, p2, p3) => (Op::Mul, vec![p1, p2, p3]),
+            Opcode::Input(p1) => (Op::In, vec![p1]),
+            Opcode::Output(p1) => (Op::Out, vec![p1]),
+            Opcode::Jit(p1, p2) => (Op::Jt, vec![p1, p2]),
+            Opcode::Jif(p1, p2) => (Op::Jf, vec![p1, p2]),
+            Opcode::Lt(p1, p2, p3) => (Op::Lt, vec![p1, p2, p3]),
+            Opcode::Eq(p1, p2, p3) => (Op::Eq, vec![p1, p2, p3]),
+            Opcode::Arb(p1) => (Op::Arb, vec![p1]),
+            Opcode::Halt => (Op::Hlt, vec![]),
+        }
+    }
+}
+
+/// An executed instruction as reported to the hook of [`Machine::run_with_hook`].
+///
+/// Its `Display` is the trace format: one line per instruction with the
+/// address, mnemonic and operands with their values resolved, so
+/// `[addr]=value` for memory and `rb+off@addr=value` for relative parameters,
+/// followed by the write if there was one. Traces of two runs can be compared
+/// with `diff`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEvent {
+    pub ip: usize,
+    pub op: Op,
+    /// The parameters as encoded in memory.
+    pub params: Vec<disasm::Operand>,
+    /// The values read by the parameters, or the address for the destination.
+    pub values: Vec<Int>,
+    /// Address and new value of the memory written.
+    pub write: Option<(usize, Int)>,
+    /// The address executed next.
+    pub next_ip: usize,
+}
+
+impl std::fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:>6} {:<3}", self.ip, self.op)?;
+        let dest = self.op.dest();
+        for (i, (param, value)) in self.params.iter().zip(&self.values).enumerate() {
+            let sep = if i == 0 { " " } else { ", " };
+            match (param, Some(i) == dest) {
+                (disasm::Operand::Imm(_), _) => write!(f, "{}{}", sep, param)?,
+                (disasm::Operand::Pos(_), true) => write!(f, "{}{}", sep, param)?,
+                (disasm::Operand::Rel(_), true) => write!(f, "{}{}@{}", sep, param, value)?,
+                _ => write!(f, "{}{}={}", sep, param, value)?,
+            }
+        }
+        if let Some((addr, value)) = self.write {
+            write!(f, " -> [{}]={}", addr, value)?;
         }
+        Ok(())
     }
 }
 
+/// Why [`Machine::resume`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunState {
+    /// The next instruction reads input but the queue is empty. Resuming after
+    /// [`Machine::push_input`] continues with that instruction.
+    NeedsInput,
+    Output(Int),
+    Halted,
+}
+
+/// Memory accessed by one instruction, see [`Machine::step_with_accesses`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Accesses {
+    /// Addresses read by the parameters with the values read.
+    pub reads: Vec<(usize, Int)>,
+    /// Addresses written with the new values.
+    pub writes: Vec<(usize, Int)>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Machine {
     mem: Vec<Int>,
     ip: usize,
     rel_base: Int,
+    input: std::collections::VecDeque<Int>,
+    /// Collects the accesses of the current instruction while it is `Some`.
+    accesses: Option<Accesses>,
 }
 
 impl std::iter::FromIterator<Int> for Machine {
@@ -102,6 +312,8 @@ impl Machine {
             mem,
             ip: 0,
             rel_base: 0,
+            input: Default::default(),
+            accesses: None,
         }
     }
     pub fn set_mem(&mut self, addr: usize, value: Int) {
@@ -110,6 +322,41 @@ impl Machine {
     pub fn get_mem(&self, addr: usize) -> Int {
         self.mem[addr]
     }
+    pub fn memory(&self) -> &[Int] {
+        &self.mem
+    }
+    /// Address of the next instruction.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+    pub fn rel_base(&self) -> Int {
+        self.rel_base
+    }
+    /// Queues a value for the next input instruction.
+    pub fn push_input(&mut self, value: Int) {
+        self.input.push_back(value);
+    }
+
+    /// Runs until the program produces output, needs input that is not queued
+    /// or halts.
+    pub fn resume(&mut self) -> Result<RunState, Error> {
+        loop {
+            if let Some(state) = self.step()? {
+                return Ok(state);
+            }
+        }
+    }
+
+    /// Runs until the next output, which is [`RunState::Output`]. A program
+    /// that halts or waits for input first returns that state instead, so a
+    /// loop feeding input can tell the two apart. The same as
+    /// [`resume`](Self::resume), named for loops that read outputs.
+    pub fn next_output(&mut self) -> Result<RunState, Error> {
+        self.resume()
+    }
+
+    /// Runs with the queued input followed by `input` and collects all output.
+    /// Returns whether the program halted, `false` means it ran out of input.
     pub fn run<T: IntoIterator<Item = Int>>(
         &mut self,
         input: T,
@@ -117,35 +364,135 @@ impl Machine {
         let mut output = Vec::new();
         let mut input_iter = input.into_iter();
         loop {
-            let op =
-                Opcode::try_from(self.read_ip_and_advance()).map_err(|e| e.add_loc(self.ip - 1))?;
-            match op {
-                Opcode::Add(p1, p2, p3) => self.bin_op(|a, b| a + b, p1, p2, p3)?,
-                Opcode::Mul(p1, p2, p3) => self.bin_op(|a, b| a * b, p1, p2, p3)?,
-                Opcode::Input(p1) => {
-                    if let Some(val) = input_iter.next() {
-                        self.input(p1, val)?
-                    } else {
-                        self.ip -= 1;
-                        return Ok((false, output));
+            match self.resume()? {
+                RunState::Output(v) => output.push(v),
+                RunState::NeedsInput => match input_iter.next() {
+                    Some(v) => self.push_input(v),
+                    None => return Ok((false, output)),
+                },
+                RunState::Halted => return Ok((true, output)),
+            }
+        }
+    }
+
+    /// Like [`run`](Self::run), but calls `hook` after every executed
+    /// instruction including the final `hlt`, e.g. to write a trace or to feed a
+    /// [`Profile`](profile::Profile).
+    pub fn run_with_hook<T: IntoIterator<Item = Int>>(
+        &mut self,
+        input: T,
+        mut hook: impl FnMut(&TraceEvent),
+    ) -> Result<(bool, Vec<Int>), Error> {
+        let mut output = Vec::new();
+        let mut input_iter = input.into_iter();
+        loop {
+            let event = self.trace_event();
+            let state = self.step()?;
+            match state {
+                Some(RunState::NeedsInput) => match input_iter.next() {
+                    Some(v) => {
+                        self.push_input(v);
+                        continue;
+                    }
+                    None => return Ok((false, output)),
+                },
+                Some(RunState::Halted) => {
+                    if let Some(event) = event {
+                        hook(&event);
                     }
-                }
-                Opcode::Output(p1) => output.push(self.output(p1)),
-                Opcode::Jit(p1, p2) => self.jump_condition(|x| x != 0, p1, p2),
-                Opcode::Jif(p1, p2) => self.jump_condition(|x| x == 0, p1, p2),
-                Opcode::Lt(p1, p2, p3) => self.bin_op(|a, b| (a < b) as Int, p1, p2, p3)?,
-                Opcode::Eq(p1, p2, p3) => self.bin_op(|a, b| (a == b) as Int, p1, p2, p3)?,
-                Opcode::Arb(p1) => self.adjust_relative_base(p1),
-                Opcode::Halt => {
-                    self.ip -= 1;
                     return Ok((true, output));
                 }
-            };
+                Some(RunState::Output(v)) => output.push(v),
+                None => {}
+            }
+            if let Some(mut event) = event {
+                if let Some(i) = event.op.dest() {
+                    let addr = event.values[i] as usize;
+                    event.write = Some((addr, self.mem[addr]));
+                }
+                event.next_ip = self.ip;
+                hook(&event);
+            }
+        }
+    }
+
+    /// The instruction at `ip` with its operands resolved, as far as it is
+    /// known before executing it.
+    fn trace_event(&self) -> Option<TraceEvent> {
+        let (op, modes) = Opcode::try_from(*self.mem.get(self.ip)?).ok()?.into_parts();
+        let dest = op.dest();
+        let mut params = Vec::new();
+        let mut values = Vec::new();
+        for (i, mode) in modes.into_iter().enumerate() {
+            let param = *self.mem.get(self.ip + 1 + i)?;
+            params.push(mode.operand(param));
+            values.push(match (&mode, Some(i) == dest) {
+                (ParamMode::Pos, true) => param,
+                (ParamMode::Rel, true) => self.rel_base + param,
+                _ => self.get_param_value(mode, param),
+            });
         }
+        Some(TraceEvent {
+            ip: self.ip,
+            op,
+            params,
+            values,
+            write: None,
+            next_ip: self.ip,
+        })
     }
 
+    /// Executes one instruction and returns the state if it stops the machine.
+    /// Instructions that wait for input, halt or fail leave `ip` unchanged.
+    pub fn step(&mut self) -> Result<Option<RunState>, Error> {
+        let ip = self.ip;
+        let word = *self.mem.get(ip).ok_or(Error::IpOutOfBounds(ip))?;
+        let op = Opcode::try_from(word).map_err(|e| e.add_loc(ip))?;
+        self.ip += 1;
+        let res = self.execute(op);
+        if !matches!(res, Ok(None | Some(RunState::Output(_)))) {
+            self.ip = ip;
+        }
+        res
+    }
+
+    /// Executes `op` with `ip` pointing at its first parameter.
+    fn execute(&mut self, op: Opcode) -> Result<Option<RunState>, Error> {
+        match op {
+            Opcode::Add(p1, p2, p3) => self.bin_op(|a, b| a + b, p1, p2, p3)?,
+            Opcode::Mul(p1, p2, p3) => self.bin_op(|a, b| a * b, p1, p2, p3)?,
+            Opcode::Input(p1) => match self.input.front() {
+                Some(&val) => {
+                    self.input(p1, val)?;
+                    self.input.pop_front();
+                }
+                None => return Ok(Some(RunState::NeedsInput)),
+            },
+            Opcode::Output(p1) => return Ok(Some(RunState::Output(self.output(p1)))),
+            Opcode::Jit(p1, p2) => self.jump_condition(|x| x != 0, p1, p2),
+            Opcode::Jif(p1, p2) => self.jump_condition(|x| x == 0, p1, p2),
+            Opcode::Lt(p1, p2, p3) => self.bin_op(|a, b| (a < b) as Int, p1, p2, p3)?,
+            Opcode::Eq(p1, p2, p3) => self.bin_op(|a, b| (a == b) as Int, p1, p2, p3)?,
+            Opcode::Arb(p1) => self.adjust_relative_base(p1),
+            Opcode::Halt => return Ok(Some(RunState::Halted)),
+        };
+        Ok(None)
+    }
+
+    /// Like [`step`](Self::step), but also returns the memory the instruction
+    /// accessed through its parameters. Instructions that wait for input or
+    /// halt access nothing.
+    pub fn step_with_accesses(&mut self) -> Result<(Option<RunState>, Accesses), Error> {
+        self.accesses = Some(Accesses::default());
+        let state = self.step();
+        let accesses = self.accesses.take().unwrap_or_default();
+        Ok((state?, accesses))
+    }
+
+    /// Reads the next parameter, which is 0 past the end of memory like any
+    /// other read.
     fn read_ip_and_advance(&mut self) -> Int {
-        let val = self.mem[self.ip];
+        let val = *self.mem.get(self.ip).unwrap_or(&0);
         self.ip += 1;
         val
     }
@@ -157,6 +504,19 @@ impl Machine {
             ParamMode::Rel => *self.mem.get((self.rel_base + param) as usize).unwrap_or(&0),
         }
     }
+    /// Reads a parameter of the executing instruction and records the access.
+    fn read_param(&mut self, pm: ParamMode, param: Int) -> Int {
+        let addr = match pm {
+            ParamMode::Pos => Some(param as usize),
+            ParamMode::Imm => None,
+            ParamMode::Rel => Some((self.rel_base + param) as usize),
+        };
+        let value = self.get_param_value(pm, param);
+        if let (Some(accesses), Some(addr)) = (&mut self.accesses, addr) {
+            accesses.reads.push((addr, value));
+        }
+        value
+    }
     fn set_param_value(&mut self, value: Int, pm: ParamMode, param: Int) -> Result<(), Error> {
         let addr = match pm {
             ParamMode::Pos => param,
@@ -167,6 +527,9 @@ impl Machine {
             self.mem.resize(addr + 1, 0);
         }
         self.mem[addr] = value;
+        if let Some(accesses) = &mut self.accesses {
+            accesses.writes.push((addr, value));
+        }
         Ok(())
     }
 
@@ -177,13 +540,13 @@ impl Machine {
     }
     fn output(&mut self, pm1: ParamMode) -> Int {
         let param = self.read_ip_and_advance();
-        self.get_param_value(pm1, param)
+        self.read_param(pm1, param)
     }
     fn jump_condition<F: FnOnce(Int) -> bool>(&mut self, cond: F, pm1: ParamMode, pm2: ParamMode) {
         let param1 = self.read_ip_and_advance();
         let param2 = self.read_ip_and_advance();
-        let val = self.get_param_value(pm1, param1);
-        let dst = self.get_param_value(pm2, param2);
+        let val = self.read_param(pm1, param1);
+        let dst = self.read_param(pm2, param2);
         if cond(val) {
             self.ip = dst as usize;
         }
@@ -198,15 +561,92 @@ impl Machine {
     ) -> Result<(), Error> {
         let param1 = self.read_ip_and_advance();
         let param2 = self.read_ip_and_advance();
-        let v1 = self.get_param_value(pm1, param1);
-        let v2 = self.get_param_value(pm2, param2);
+        let v1 = self.read_param(pm1, param1);
+        let v2 = self.read_param(pm2, param2);
         let result = op(v1, v2);
         let dest = self.read_ip_and_advance();
         self.set_param_value(result, pm3, dest)
     }
     fn adjust_relative_base(&mut self, pm1: ParamMode) {
         let param = self.read_ip_and_advance();
-        let delta = self.get_param_value(pm1, param);
+        let delta = self.read_param(pm1, param);
         self.rel_base += delta;
     }
 }
+
+/// Reads a number and prints it and its double until it reads 0. Shared by the
+/// tests of the machine and its tools.
+#[cfg(test)]
+pub(crate) const DOUBLER: [Int; 19] = [
+    3, 100, 1006, 100, 18, 4, 100, 1002, 100, 2, 100, 4, 100, 1105, 1, 0, 0, 0, 99,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interleaves_input_and_output() {
+        let mut m = Machine::from_vec(DOUBLER.to_vec());
+        assert_eq!(m.resume().unwrap(), RunState::NeedsInput);
+        assert_eq!(m.resume().unwrap(), RunState::NeedsInput);
+        m.push_input(21);
+        assert_eq!(m.next_output().unwrap(), RunState::Output(21));
+        assert_eq!(m.next_output().unwrap(), RunState::Output(42));
+        assert_eq!(m.next_output().unwrap(), RunState::NeedsInput);
+        m.push_input(0);
+        assert_eq!(m.next_output().unwrap(), RunState::Halted);
+        assert_eq!(m.resume().unwrap(), RunState::Halted);
+    }
+
+    #[test]
+    fn feeds_input_until_halted() {
+        let mut m = Machine::from_vec(DOUBLER.to_vec());
+        let mut input = vec![0, 5, 1];
+        let mut output = Vec::new();
+        loop {
+            match m.next_output().unwrap() {
+                RunState::Output(v) => output.push(v),
+                RunState::NeedsInput => m.push_input(input.pop().unwrap()),
+                RunState::Halted => break,
+            }
+        }
+        assert_eq!(output, [1, 2, 5, 10]);
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn runs_with_input_iterator() {
+        let mut m = Machine::from_vec(DOUBLER.to_vec());
+        assert_eq!(m.run([1, 5]).unwrap(), (false, vec![1, 2, 5, 10]));
+        m.push_input(3);
+        assert_eq!(m.run([0]).unwrap(), (true, vec![3, 6]));
+    }
+
+    #[test]
+    fn traces_executed_instructions() {
+        let mut m = Machine::from_vec(DOUBLER.to_vec());
+        let mut trace = Vec::new();
+        let result = m.run_with_hook([4, 0], |e| trace.push(e.to_string()));
+        assert_eq!(result.unwrap(), (true, vec![4, 8]));
+        assert_eq!(
+            trace[..7],
+            [
+                "     0 in  [100] -> [100]=4",
+                "     2 jf  [100]=4, #18",
+                "     5 out [100]=4",
+                "     7 mul [100]=4, #2, [100] -> [100]=8",
+                "    11 out [100]=8",
+                "    13 jt  #1, #0",
+                "     0 in  [100] -> [100]=0",
+            ]
+        );
+        assert_eq!(trace[7..], ["     2 jf  [100]=0, #18", "    18 hlt"]);
+
+        let mut m = Machine::from_vec(vec![109, 7, 21101, 2, 3, 1, 99, 0, 0]);
+        let mut trace = Vec::new();
+        m.run_with_hook(None, |e| trace.push(e.to_string()))
+            .unwrap();
+        assert_eq!(trace[1], "     2 add #2, #3, rb+1@8 -> [8]=5");
+    }
+}
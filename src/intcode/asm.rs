@@ -0,0 +1,468 @@
+//! Assembler for the syntax of [`disasm`](super::disasm) listings, so that
+//! `assemble(&disassemble(mem).to_string())` gives back `mem`.
+//!
+//! ```text
+//! .macro mov src, dst         ; operands of a macro are substituted by name,
+//!         add src, #0, dst    ; `@` is replaced by a suffix unique to each use
+//! .endm
+//!         arb #msg
+//! loop@:  jf rb+0, #end       ; operands are [addr], #imm or rb+off and take
+//!         out rb+0            ; numbers, labels and sums of them like msg+1
+//!         arb #1
+//!         jt #1, #loop@
+//! end:    hlt
+//! msg:    .data "Hi\n", 0     ; strings are stored one character per cell
+//! ```
+use super::{Int, Op};
+use std::collections::HashMap;
+
+/// Why assembling failed, with the number of the source line starting at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsmError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for AsmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for AsmError {}
+
+fn error(line: usize, message: impl std::fmt::Display) -> AsmError {
+    AsmError {
+        line,
+        message: message.to_string(),
+    }
+}
+
+/// Macros may use other macros up to this depth.
+const MAX_MACRO_DEPTH: usize = 16;
+
+struct Macro {
+    params: Vec<String>,
+    body: Vec<(usize, String)>,
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Calls `f` for every character with whether it is part of a string literal.
+fn scan_strings(s: &str, mut f: impl FnMut(usize, char, bool) -> bool) {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        let quote = c == '"' && !escaped;
+        if !f(i, c, in_string || quote) {
+            return;
+        }
+        escaped = in_string && c == '\\' && !escaped;
+        if quote {
+            in_string = !in_string;
+        }
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut end = line.len();
+    scan_strings(line, |i, c, in_string| {
+        if c == ';' && !in_string {
+            end = i;
+            return false;
+        }
+        true
+    });
+    &line[..end]
+}
+
+/// Splits at commas outside of string literals.
+fn split_args(s: &str) -> Vec<String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Vec::new();
+    }
+    let mut res = vec![String::new()];
+    scan_strings(s, |_, c, in_string| {
+        if c == ',' && !in_string {
+            res.push(String::new());
+        } else if let Some(last) = res.last_mut() {
+            last.push(c);
+        }
+        true
+    });
+    res.iter().map(|a| a.trim().to_string()).collect()
+}
+
+/// Splits the leading `label:` definitions from the statement of a line.
+fn split_labels(mut line: &str) -> (Vec<String>, &str) {
+    let mut labels = Vec::new();
+    while let Some((label, rest)) = line.split_once(':') {
+        if !is_ident(label.trim()) {
+            break;
+        }
+        labels.push(label.trim().to_string());
+        line = rest;
+    }
+    (labels, line.trim())
+}
+
+/// Replaces the parameters of a macro by its arguments and `@` by `_<id>`.
+fn substitute(line: &str, params: &[String], args: &[String], id: usize) -> String {
+    let mut res = String::new();
+    let mut word = String::new();
+    let flush = |word: &mut String, res: &mut String| {
+        match params.iter().position(|p| p == word) {
+            Some(i) => res.push_str(&args[i]),
+            None => res.push_str(word),
+        }
+        word.clear();
+    };
+    scan_strings(line, |_, c, in_string| {
+        if !in_string && (c.is_ascii_alphanumeric() || c == '_') {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut res);
+            if c == '@' && !in_string {
+                res.push_str(&format!("_{}", id));
+            } else {
+                res.push(c);
+            }
+        }
+        true
+    });
+    flush(&mut word, &mut res);
+    res
+}
+
+/// Splits a statement into its mnemonic or directive and the arguments.
+fn split_statement(statement: &str) -> (&str, Vec<String>) {
+    let (head, rest) = statement
+        .split_once(char::is_whitespace)
+        .unwrap_or((statement, ""));
+    (head, split_args(rest))
+}
+
+struct Expander<'a> {
+    macros: &'a HashMap<String, Macro>,
+    uses: usize,
+    lines: Vec<(usize, String)>,
+}
+
+impl Expander<'_> {
+    fn expand(&mut self, number: usize, line: &str, depth: usize) -> Result<(), AsmError> {
+        let (labels, statement) = split_labels(line);
+        let (head, args) = split_statement(statement);
+        let mac = match self.macros.get(head) {
+            Some(mac) => mac,
+            None => {
+                self.lines.push((number, line.to_string()));
+                return Ok(());
+            }
+        };
+        if depth >= MAX_MACRO_DEPTH {
+            return Err(error(number, format!("macro {} nested too deeply", head)));
+        }
+        if args.len() != mac.params.len() {
+            return Err(error(
+                number,
+                format!("macro {} takes {} arguments", head, mac.params.len()),
+            ));
+        }
+        if !labels.is_empty() {
+            self.lines.push((number, format!("{}:", labels.join(": "))));
+        }
+        self.uses += 1;
+        let id = self.uses;
+        for (_, body) in &mac.body {
+            let body = substitute(body, &mac.params, &args, id);
+            self.expand(number, &body, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// The characters of a string literal with escapes like `\n` resolved.
+pub(super) fn parse_string(s: &str) -> Option<Vec<Int>> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut res = Vec::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        let c = match c {
+            '\\' => match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                '0' => '\0',
+                c => c,
+            },
+            c => c,
+        };
+        res.push(c as Int);
+    }
+    Some(res)
+}
+
+/// Evaluates a sum of numbers and labels like `msg+1` or `-3`.
+fn eval(expr: &str, labels: &HashMap<String, usize>) -> Result<Int, String> {
+    let mut res = 0;
+    let mut rest = expr.trim();
+    let mut sign = 1;
+    loop {
+        if let Some(r) = rest.strip_prefix('-') {
+            sign = -sign;
+            rest = r.trim_start();
+            continue;
+        }
+        if let Some(r) = rest.strip_prefix('+') {
+            rest = r.trim_start();
+            continue;
+        }
+        let end = rest.find(['+', '-']).unwrap_or(rest.len());
+        let term = rest[..end].trim();
+        let value = if let Ok(v) = term.parse::<Int>() {
+            v
+        } else if let Some(addr) = labels.get(term) {
+            *addr as Int
+        } else if is_ident(term) {
+            return Err(format!("unknown label {}", term));
+        } else {
+            return Err(format!("invalid expression {}", expr.trim()));
+        };
+        res += sign * value;
+        sign = 1;
+        rest = &rest[end..];
+        if rest.is_empty() {
+            return Ok(res);
+        }
+    }
+}
+
+/// The mode digit and value of an operand.
+fn operand(arg: &str, labels: &HashMap<String, usize>) -> Result<(Int, Int), String> {
+    if let Some(addr) = arg.strip_prefix('[').and_then(|a| a.strip_suffix(']')) {
+        Ok((0, eval(addr, labels)?))
+    } else if let Some(imm) = arg.strip_prefix('#') {
+        Ok((1, eval(imm, labels)?))
+    } else if let Some(off) = arg.strip_prefix("rb") {
+        let off = off.trim();
+        if off.is_empty() {
+            Ok((2, 0))
+        } else if off.starts_with(['+', '-']) {
+            Ok((2, eval(off, labels)?))
+        } else {
+            Err(format!("invalid operand {}", arg))
+        }
+    } else {
+        Err(format!(
+            "invalid operand {}, expected [addr], #imm or rb+off",
+            arg
+        ))
+    }
+}
+
+/// Assembles `source` into memory for [`Machine::from_vec`](super::Machine::from_vec).
+pub fn assemble(source: &str) -> Result<Vec<Int>, AsmError> {
+    // Collect the macro definitions, which can be anywhere in the source.
+    let mut macros = HashMap::new();
+    let mut program = Vec::new();
+    let mut current: Option<(usize, String, Macro)> = None;
+    for (i, line) in source.lines().enumerate() {
+        let number = i + 1;
+        let line = strip_comment(line).trim();
+        let head = line.split_whitespace().next().unwrap_or_default();
+        match (head, current.as_mut()) {
+            (".macro", None) => {
+                let (name, params) = split_statement(line[".macro".len()..].trim());
+                if !is_ident(name) || !params.iter().all(|p| is_ident(p)) {
+                    return Err(error(number, "invalid macro definition"));
+                }
+                let mac = Macro {
+                    params,
+                    body: Vec::new(),
+                };
+                current = Some((number, name.to_string(), mac));
+            }
+            (".macro", Some(_)) => return Err(error(number, "macros cannot be nested")),
+            (".endm", Some(_)) => {
+                if let Some((_, name, mac)) = current.take() {
+                    macros.insert(name, mac);
+                }
+            }
+            (".endm", None) => return Err(error(number, ".endm without .macro")),
+            (_, Some((_, _, mac))) => mac.body.push((number, line.to_string())),
+            (_, None) => program.push((number, line.to_string())),
+        }
+    }
+    if let Some((number, name, _)) = current {
+        return Err(error(number, format!("macro {} is missing .endm", name)));
+    }
+
+    let mut expander = Expander {
+        macros: &macros,
+        uses: 0,
+        lines: Vec::new(),
+    };
+    for (number, line) in &program {
+        expander.expand(*number, line, 0)?;
+    }
+
+    // Assign addresses to labels, then encode with all labels known.
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+    let mut addr = 0;
+    for (number, line) in &expander.lines {
+        let (defs, statement) = split_labels(line);
+        for label in defs {
+            if label == "rb" {
+                return Err(error(*number, "rb cannot be used as a label"));
+            }
+            if labels.insert(label.clone(), addr).is_some() {
+                return Err(error(*number, format!("label {} defined twice", label)));
+            }
+        }
+        if statement.is_empty() {
+            continue;
+        }
+        let (head, args) = split_statement(statement);
+        addr += match head {
+            ".data" => args
+                .iter()
+                .map(|a| parse_string(a).map(|s| s.len()).unwrap_or(1))
+                .sum(),
+            _ => match Op::ALL.into_iter().find(|op| op.mnemonic() == head) {
+                Some(op) if op.param_count() == args.len() => 1 + args.len(),
+                Some(op) => {
+                    let count = op.param_count();
+                    return Err(error(*number, format!("{} takes {} operands", head, count)));
+                }
+                None => return Err(error(*number, format!("unknown instruction {}", head))),
+            },
+        };
+        statements.push((*number, head, args));
+    }
+
+    let mut mem = Vec::with_capacity(addr);
+    for (number, head, args) in statements {
+        if head == ".data" {
+            for arg in &args {
+                match parse_string(arg) {
+                    Some(s) => mem.extend(s),
+                    None => mem.push(eval(arg, &labels).map_err(|e| error(number, e))?),
+                }
+            }
+            continue;
+        }
+        let op = Op::ALL
+            .into_iter()
+            .find(|op| op.mnemonic() == head)
+            .map_or(0, Op::code);
+        let mut word = op;
+        let mut values = Vec::new();
+        for (i, arg) in args.iter().enumerate() {
+            let (mode, value) = operand(arg, &labels).map_err(|e| error(number, e))?;
+            word += mode * 10_i64.pow(2 + i as u32);
+            values.push(value);
+        }
+        mem.push(word);
+        mem.extend(values);
+    }
+    Ok(mem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::disasm::disassemble;
+    use crate::intcode::Machine;
+
+    fn output(mem: Vec<Int>) -> Vec<Int> {
+        let (halted, output) = Machine::from_vec(mem).run(None).unwrap();
+        assert!(halted);
+        output
+    }
+
+    #[test]
+    fn assembles_strings_and_labels() {
+        let mem = assemble(
+            "        arb #msg        ; print the message
+            loop:   jf rb+0, #end
+                    out rb
+                    arb #1
+                    jt #1, #loop
+            end:    hlt
+            msg:    .data \"Hi;\\n\", 0",
+        )
+        .unwrap();
+        assert_eq!(&mem[..3], [109, 13, 1206]);
+        let text: String = output(mem).iter().map(|c| *c as u8 as char).collect();
+        assert_eq!(text, "Hi;\n");
+    }
+
+    #[test]
+    fn expands_macros() {
+        let mem = assemble(
+            ".macro countdown addr
+            top@:   out addr
+                    add addr, #-1, addr
+                    jt addr, #top@
+            .endm
+            .macro twice a, b
+                    countdown a
+                    countdown b
+            .endm
+                    twice [a], [b]
+                    hlt
+            a:      .data 2
+            b:      .data 1",
+        )
+        .unwrap();
+        assert_eq!(output(mem), [2, 1, 1]);
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(
+            assemble("add #1, #2\n").unwrap_err().to_string(),
+            "line 1: add takes 3 operands"
+        );
+        assert_eq!(assemble("\n jt #1, #nowhere").unwrap_err().line, 2);
+        assert_eq!(assemble("x: hlt\nx: hlt").unwrap_err().line, 2);
+        assert!(assemble(".macro m\nhlt").is_err());
+        assert!(assemble(".macro m\nm\n.endm\nm").is_err());
+        assert!(assemble("out 5").is_err());
+    }
+
+    #[test]
+    fn round_trips_disassembly() {
+        let mut state = 7_u64;
+        let mut random = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as Int
+        };
+        for _ in 0..50 {
+            // Mostly valid opcodes and mode digits, some negative data.
+            let mem: Vec<Int> = (0..60)
+                .map(|_| match random() % 4 {
+                    0 => random() % 100 - 20,
+                    1 => {
+                        [1, 2, 7, 8][random() as usize % 4]
+                            + 100 * (random() % 3)
+                            + 1000 * (random() % 3)
+                    }
+                    2 => {
+                        [5, 6][random() as usize % 2] + 100 * (random() % 3) + 1000 * (random() % 2)
+                    }
+                    _ => [3, 4, 9, 99, 203, 104, 109, 1105][random() as usize % 8],
+                })
+                .collect();
+            let listing = disassemble(&mem).to_string();
+            assert_eq!(assemble(&listing).unwrap(), mem, "{}", listing);
+        }
+    }
+}
@@ -0,0 +1,576 @@
+//! Debugger for a [`Machine`] with single steps, breakpoints on addresses and
+//! watchpoints on memory reads and writes, plus a small command line for it.
+use super::asm::parse_string;
+use super::disasm::{decode, Instruction};
+use super::{Error, Int, Machine, RunState};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{BufRead, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+/// Which accesses of a watched address stop the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Watch {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl Watch {
+    fn matches(self, access: Access) -> bool {
+        matches!(
+            (self, access),
+            (Self::ReadWrite, _) | (Self::Read, Access::Read) | (Self::Write, Access::Write)
+        )
+    }
+}
+
+/// What a single instruction did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepInfo {
+    pub addr: usize,
+    /// `None` if its operands run past the end of memory.
+    pub instruction: Option<Instruction>,
+    /// Memory read by the operands with the values read, as resolved by the
+    /// machine.
+    pub reads: Vec<(usize, Int)>,
+    /// Memory written with the new values.
+    pub writes: Vec<(usize, Int)>,
+    /// Set if the instruction produced output, waited for input or halted.
+    pub state: Option<RunState>,
+}
+
+/// Why [`Debugger::cont`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    Breakpoint(usize),
+    /// The instruction at `ip` accessed the watched `addr`.
+    Watchpoint {
+        ip: usize,
+        addr: usize,
+        access: Access,
+        value: Int,
+    },
+    NeedsInput,
+    Halted,
+}
+
+impl std::fmt::Display for Stop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Breakpoint(addr) => write!(f, "breakpoint at {}", addr),
+            Self::Watchpoint {
+                ip,
+                addr,
+                access,
+                value,
+            } => {
+                let access = match access {
+                    Access::Read => "read",
+                    Access::Write => "write",
+                };
+                write!(f, "watchpoint: {} [{}] = {} at {}", access, addr, value, ip)
+            }
+            Self::NeedsInput => write!(f, "waiting for input"),
+            Self::Halted => write!(f, "halted"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    machine: Machine,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeMap<usize, Watch>,
+    output: Vec<Int>,
+    steps: u64,
+}
+
+impl Debugger {
+    pub fn new(machine: Machine) -> Self {
+        Self {
+            machine,
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeMap::new(),
+            output: Vec::new(),
+            steps: 0,
+        }
+    }
+    pub fn machine(&self) -> &Machine {
+        &self.machine
+    }
+    /// Number of instructions executed so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+    pub fn push_input(&mut self, value: Int) {
+        self.machine.push_input(value);
+    }
+    /// Output produced since the last call.
+    pub fn take_output(&mut self) -> Vec<Int> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Memory at `addr`, which is 0 beyond the end like for the machine.
+    pub fn read(&self, addr: usize) -> Int {
+        self.machine.memory().get(addr).copied().unwrap_or(0)
+    }
+
+    pub fn set_breakpoint(&mut self, addr: usize) {
+        self.breakpoints.insert(addr);
+    }
+    pub fn set_watchpoint(&mut self, addr: usize, watch: Watch) {
+        self.watchpoints.insert(addr, watch);
+    }
+    /// Removes the breakpoint and watchpoint at `addr`, returns whether there was one.
+    pub fn clear(&mut self, addr: usize) -> bool {
+        let breakpoint = self.breakpoints.remove(&addr);
+        self.watchpoints.remove(&addr).is_some() || breakpoint
+    }
+    pub fn breakpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.breakpoints.iter().copied()
+    }
+    pub fn watchpoints(&self) -> impl Iterator<Item = (usize, Watch)> + '_ {
+        self.watchpoints.iter().map(|(a, w)| (*a, *w))
+    }
+
+    /// Executes a single instruction.
+    pub fn step(&mut self) -> Result<StepInfo, Error> {
+        let addr = self.machine.ip();
+        let instruction = decode(self.machine.memory(), addr);
+        let (state, accesses) = self.machine.step_with_accesses()?;
+        let info = StepInfo {
+            addr,
+            instruction,
+            reads: accesses.reads,
+            writes: accesses.writes,
+            state,
+        };
+        match state {
+            Some(RunState::NeedsInput) | Some(RunState::Halted) => return Ok(info),
+            Some(RunState::Output(v)) => self.output.push(v),
+            None => {}
+        }
+        self.steps += 1;
+        Ok(info)
+    }
+
+    /// Runs until a breakpoint or watchpoint is hit, the program needs input
+    /// or it halts. A breakpoint at the current instruction is ignored, so
+    /// that continuing from it makes progress.
+    pub fn cont(&mut self) -> Result<Stop, Error> {
+        let mut first = true;
+        loop {
+            let ip = self.machine.ip();
+            if !first && self.breakpoints.contains(&ip) {
+                return Ok(Stop::Breakpoint(ip));
+            }
+            first = false;
+            let info = self.step()?;
+            match info.state {
+                Some(RunState::NeedsInput) => return Ok(Stop::NeedsInput),
+                Some(RunState::Halted) => return Ok(Stop::Halted),
+                _ => {}
+            }
+            let accesses = info
+                .reads
+                .iter()
+                .map(|a| (Access::Read, a))
+                .chain(info.writes.iter().map(|a| (Access::Write, a)));
+            for (access, &(addr, value)) in accesses {
+                if let Some(watch) = self.watchpoints.get(&addr) {
+                    if watch.matches(access) {
+                        return Ok(Stop::Watchpoint {
+                            ip,
+                            addr,
+                            access,
+                            value,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads commands from `input` until it ends or `quit`, see `help` for the
+    /// list. `prompt` shows a prompt before each command for interactive use.
+    pub fn repl(
+        &mut self,
+        input: impl BufRead,
+        out: &mut impl Write,
+        prompt: bool,
+    ) -> std::io::Result<()> {
+        if prompt {
+            write!(out, "(icdb) ")?;
+            out.flush()?;
+        }
+        for line in input.lines() {
+            let line = line?;
+            let mut args = line.split_whitespace();
+            let cmd = args.next().unwrap_or_default();
+            let args: Vec<&str> = args.collect();
+            match cmd {
+                "" => {}
+                "q" | "quit" => return Ok(()),
+                "h" | "help" => writeln!(out, "{}", HELP)?,
+                _ => match self.command(cmd, &args, line.trim()[cmd.len()..].trim(), out) {
+                    Ok(()) => {}
+                    Err(CmdError::Io(e)) => return Err(e),
+                    Err(CmdError::Message(e)) => writeln!(out, "error: {}", e)?,
+                },
+            }
+            self.print_output(out)?;
+            if prompt {
+                write!(out, "(icdb) ")?;
+                out.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn command(
+        &mut self,
+        cmd: &str,
+        args: &[&str],
+        rest: &str,
+        out: &mut impl Write,
+    ) -> Result<(), CmdError> {
+        let number = |i: usize, default: Option<usize>| -> Result<usize, String> {
+            match args.get(i) {
+                Some(a) => a.parse().map_err(|_| format!("not an address: {}", a)),
+                None => default.ok_or_else(|| format!("{} needs an address", cmd)),
+            }
+        };
+        match cmd {
+            "s" | "step" => {
+                for _ in 0..number(0, Some(1))? {
+                    let info = self.step()?;
+                    write_step(&info, out)?;
+                    if info.state == Some(RunState::NeedsInput) {
+                        writeln!(out, "{}", Stop::NeedsInput)?;
+                        break;
+                    }
+                    if info.state == Some(RunState::Halted) {
+                        writeln!(out, "{}", Stop::Halted)?;
+                        break;
+                    }
+                }
+            }
+            "c" | "continue" => {
+                let stop = self.cont()?;
+                self.print_output(out)?;
+                writeln!(out, "{}", stop)?;
+            }
+            "b" | "break" => {
+                let addr = number(0, None)?;
+                self.set_breakpoint(addr);
+                writeln!(out, "breakpoint at {}", addr)?;
+            }
+            "w" | "watch" => {
+                let addr = number(0, None)?;
+                let watch = match args.get(1).copied().unwrap_or("rw") {
+                    "r" => Watch::Read,
+                    "w" => Watch::Write,
+                    "rw" => Watch::ReadWrite,
+                    a => return Err(format!("expected r, w or rw instead of {}", a).into()),
+                };
+                self.set_watchpoint(addr, watch);
+                writeln!(out, "watchpoint at {} ({:?})", addr, watch)?;
+            }
+            "d" | "delete" => {
+                let addr = number(0, None)?;
+                if self.clear(addr) {
+                    writeln!(out, "deleted {}", addr)?;
+                } else {
+                    writeln!(out, "nothing set at {}", addr)?;
+                }
+            }
+            "i" | "info" => {
+                writeln!(
+                    out,
+                    "ip {}  rb {}  steps {}",
+                    self.machine.ip(),
+                    self.machine.rel_base(),
+                    self.steps
+                )?;
+                let breakpoints: Vec<String> = self.breakpoints().map(|a| a.to_string()).collect();
+                if !breakpoints.is_empty() {
+                    writeln!(out, "breakpoints {}", breakpoints.join(", "))?;
+                }
+                let watchpoints: Vec<String> = self
+                    .watchpoints()
+                    .map(|(a, w)| format!("{} ({:?})", a, w))
+                    .collect();
+                if !watchpoints.is_empty() {
+                    writeln!(out, "watchpoints {}", watchpoints.join(", "))?;
+                }
+            }
+            "x" | "mem" => {
+                let start = number(0, None)?;
+                let len = number(1, Some(1))?;
+                let size = self.machine.memory().len();
+                if start >= size {
+                    return Err(format!("{} is outside memory of size {}", start, size).into());
+                }
+                let end = start.checked_add(len).map_or(size, |end| end.min(size));
+                let values = &self.machine.memory()[start..end];
+                for (i, chunk) in values.chunks(8).enumerate() {
+                    let chunk: Vec<String> = chunk.iter().map(Int::to_string).collect();
+                    writeln!(out, "{:>6}: {}", start + 8 * i, chunk.join(" "))?;
+                }
+            }
+            "l" | "list" => {
+                let mut addr = number(0, Some(self.machine.ip()))?;
+                for _ in 0..number(1, Some(5))? {
+                    let marker = if addr == self.machine.ip() { '>' } else { ' ' };
+                    match decode(self.machine.memory(), addr) {
+                        Some(ins) => {
+                            writeln!(out, "{}{:>5}: {}", marker, addr, ins)?;
+                            addr += ins.size();
+                        }
+                        None => {
+                            writeln!(out, "{}{:>5}: .data {}", marker, addr, self.read(addr))?;
+                            addr += 1;
+                        }
+                    }
+                }
+            }
+            "in" | "input" => {
+                if rest.starts_with('"') {
+                    let text = parse_string(rest).ok_or("invalid string")?;
+                    text.into_iter().for_each(|c| self.push_input(c));
+                } else {
+                    for a in args {
+                        let value = a
+                            .parse::<Int>()
+                            .map_err(|_| format!("not a number: {}", a))?;
+                        self.push_input(value);
+                    }
+                }
+            }
+            _ => return Err(format!("unknown command {}, try help", cmd).into()),
+        }
+        Ok(())
+    }
+
+    fn print_output(&mut self, out: &mut impl Write) -> std::io::Result<()> {
+        let output = self.take_output();
+        if output.is_empty() {
+            return Ok(());
+        }
+        let values: Vec<String> = output.iter().map(Int::to_string).collect();
+        write!(out, "output {}", values.join(", "))?;
+        let text: Option<String> = output
+            .iter()
+            .map(|v| {
+                u8::try_from(*v)
+                    .ok()
+                    .filter(|c| c.is_ascii())
+                    .map(char::from)
+            })
+            .collect();
+        match text {
+            Some(text) => writeln!(out, " {:?}", text),
+            None => writeln!(out),
+        }
+    }
+}
+
+/// Failure of a debugger command. Messages are shown to the user, I/O errors
+/// end the command line.
+enum CmdError {
+    Io(std::io::Error),
+    Message(String),
+}
+
+impl From<std::io::Error> for CmdError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+impl From<String> for CmdError {
+    fn from(e: String) -> Self {
+        Self::Message(e)
+    }
+}
+impl From<&str> for CmdError {
+    fn from(e: &str) -> Self {
+        Self::Message(e.to_string())
+    }
+}
+impl From<Error> for CmdError {
+    fn from(e: Error) -> Self {
+        Self::Message(e.to_string())
+    }
+}
+
+fn write_step(info: &StepInfo, out: &mut impl Write) -> std::io::Result<()> {
+    match &info.instruction {
+        Some(ins) => writeln!(out, "{:>6}: {}", info.addr, ins)?,
+        None => writeln!(out, "{:>6}: ?", info.addr)?,
+    }
+    for (addr, value) in &info.reads {
+        writeln!(out, "        read [{}] = {}", addr, value)?;
+    }
+    for (addr, value) in &info.writes {
+        writeln!(out, "        write [{}] = {}", addr, value)?;
+    }
+    Ok(())
+}
+
+const HELP: &str = "s, step [N]           execute N instructions, 1 by default
+c, continue           run until a breakpoint, watchpoint, input or halt
+b, break ADDR         stop before executing ADDR
+w, watch ADDR [r|w|rw] stop after ADDR is read and/or written
+d, delete ADDR        remove the breakpoint and watchpoint at ADDR
+i, info               show ip, relative base, breakpoints and watchpoints
+x, mem ADDR [LEN]     show LEN values of memory starting at ADDR
+l, list [ADDR] [N]    disassemble N instructions from ADDR, default ip
+in, input VALUE...    queue numbers or a \"string\" as input
+q, quit               leave the debugger";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::asm::assemble;
+    use crate::intcode::DOUBLER;
+
+    fn debugger() -> Debugger {
+        Debugger::new(Machine::from_vec(DOUBLER.to_vec()))
+    }
+
+    #[test]
+    fn stops_at_breakpoints_and_watchpoints() {
+        let mut dbg = debugger();
+        assert_eq!(dbg.cont().unwrap(), Stop::NeedsInput);
+        dbg.push_input(3);
+        dbg.push_input(0);
+        dbg.set_breakpoint(5);
+        assert_eq!(dbg.cont().unwrap(), Stop::Breakpoint(5));
+        dbg.set_watchpoint(100, Watch::Write);
+        assert_eq!(
+            dbg.cont().unwrap(),
+            Stop::Watchpoint {
+                ip: 7,
+                addr: 100,
+                access: Access::Write,
+                value: 6,
+            }
+        );
+        assert_eq!(dbg.take_output(), [3]);
+        dbg.clear(100);
+        assert_eq!(dbg.cont().unwrap(), Stop::Halted);
+        assert_eq!(dbg.take_output(), [6]);
+        assert_eq!(dbg.machine().ip(), 18);
+    }
+
+    #[test]
+    fn watches_relative_reads() {
+        let program = assemble("arb #10\nout rb+5\nhlt").unwrap();
+        let mut dbg = Debugger::new(Machine::from_vec(program));
+        dbg.set_watchpoint(15, Watch::Read);
+        assert_eq!(
+            dbg.cont().unwrap(),
+            Stop::Watchpoint {
+                ip: 2,
+                addr: 15,
+                access: Access::Read,
+                value: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn reports_jumps_out_of_memory() {
+        let mut dbg = Debugger::new(Machine::from_vec(vec![1105, 1, 100]));
+        assert_eq!(dbg.step().unwrap().addr, 0);
+        assert_eq!(dbg.machine().ip(), 100);
+        assert!(matches!(dbg.step(), Err(Error::IpOutOfBounds(100))));
+        assert!(matches!(dbg.cont(), Err(Error::IpOutOfBounds(100))));
+        let mut out = Vec::new();
+        let mut dbg = Debugger::new(Machine::from_vec(vec![1105, 1, 100]));
+        dbg.repl("s\ns\n".as_bytes(), &mut out, false).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "     0: jt #1, #100
+error: instruction pointer 100 is outside memory
+"
+        );
+    }
+
+    #[test]
+    fn stays_at_failing_instructions() {
+        let mut dbg = Debugger::new(Machine::from_vec(vec![42, 0]));
+        assert!(matches!(dbg.step(), Err(Error::IllegalOp(42, Some(0)))));
+        assert_eq!(dbg.machine().ip(), 0);
+        assert_eq!(dbg.steps(), 0);
+        // Writing to an immediate fails without consuming the input.
+        let mut dbg = Debugger::new(Machine::from_vec(vec![104, 1, 103, 5, 99]));
+        dbg.push_input(7);
+        dbg.step().unwrap();
+        assert!(matches!(dbg.step(), Err(Error::TriedToWriteImmediate)));
+        assert_eq!(dbg.machine().ip(), 2);
+        let mut out = Vec::new();
+        let mut dbg = Debugger::new(Machine::from_vec(vec![42, 0]));
+        dbg.repl("s\ni\n".as_bytes(), &mut out, false).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "error: illegal opcode 42 at 0\nip 0  rb 0  steps 0\n"
+        );
+    }
+
+    #[test]
+    fn runs_scripted_commands() {
+        let script = "
+            break 5
+            continue
+            input 21 0
+            c
+            info
+            s 2
+            x 99 2
+            x 100 18446744073709551615
+            x 101
+            watch 100 r
+            l 13 4
+            bogus
+            c
+            d 100
+            c
+        ";
+        let mut out = Vec::new();
+        debugger().repl(script.as_bytes(), &mut out, false).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "breakpoint at 5
+waiting for input
+breakpoint at 5
+ip 5  rb 0  steps 2
+breakpoints 5
+     5: out [100]
+        read [100] = 21
+     7: mul [100], #2, [100]
+        read [100] = 21
+        write [100] = 42
+output 21 \"\\u{15}\"
+    99: 0 42
+   100: 42
+error: 101 is outside memory of size 101
+watchpoint at 100 (Read)
+    13: jt #1, #0
+    16: .data 0
+    17: .data 0
+    18: hlt
+error: unknown command bogus, try help
+output 42 \"*\"
+watchpoint: read [100] = 42 at 11
+deleted 100
+halted
+"
+        );
+    }
+}
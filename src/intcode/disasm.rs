@@ -0,0 +1,244 @@
+//! Turns intcode memory back into a listing that the assembler accepts.
+//!
+//! Code is found by following the control flow from address 0, everything
+//! that is never reached is shown as `.data`. Jumps with an immediate target
+//! get a label `l<addr>` at that address.
+use super::{Int, Op, Opcode};
+use std::collections::{BTreeSet, HashMap};
+
+/// A parameter as written in a listing: `[addr]`, `#imm` or `rb+off`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Pos(Int),
+    Imm(Int),
+    Rel(Int),
+}
+
+impl std::fmt::Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pos(v) => write!(f, "[{}]", v),
+            Self::Imm(v) => write!(f, "#{}", v),
+            Self::Rel(v) if *v < 0 => write!(f, "rb{}", v),
+            Self::Rel(v) => write!(f, "rb+{}", v),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub op: Op,
+    pub operands: Vec<Operand>,
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.op)?;
+        for (i, op) in self.operands.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { " " } else { ", " }, op)?;
+        }
+        Ok(())
+    }
+}
+
+impl Instruction {
+    /// Number of memory cells the instruction occupies.
+    pub fn size(&self) -> usize {
+        1 + self.operands.len()
+    }
+
+    /// Addresses that can be executed after this instruction at `addr`.
+    /// Jumps to computed addresses cannot be followed.
+    fn successors(&self, addr: usize) -> Vec<usize> {
+        let next = addr + self.size();
+        match (self.op, self.operands.as_slice()) {
+            (Op::Hlt, _) => vec![],
+            (jump @ (Op::Jt | Op::Jf), [cond, target]) => {
+                // With an immediate condition the jump is always or never taken.
+                let taken = match cond {
+                    Operand::Imm(v) => Some((*v != 0) == (jump == Op::Jt)),
+                    _ => None,
+                };
+                let mut res = Vec::new();
+                if taken != Some(true) {
+                    res.push(next);
+                }
+                if let (Operand::Imm(t), false) = (target, taken == Some(false)) {
+                    res.extend(usize::try_from(*t).ok());
+                }
+                res
+            }
+            _ => vec![next],
+        }
+    }
+
+    /// The target of a jump with an immediate destination.
+    fn jump_target(&self) -> Option<usize> {
+        match (self.op, self.operands.as_slice()) {
+            (Op::Jt | Op::Jf, [_, Operand::Imm(t)]) => usize::try_from(*t).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes the instruction at `addr` the way the machine does, so it fails
+/// exactly for the words the machine rejects and for truncated operands.
+///
+/// Mode digits the machine ignores, like the third one of a jump, are dropped,
+/// so such an instruction reassembles to the shorter word.
+pub fn decode(mem: &[Int], addr: usize) -> Option<Instruction> {
+    let (op, modes) = Opcode::try_from(*mem.get(addr)?).ok()?.into_parts();
+    let operands = modes
+        .iter()
+        .enumerate()
+        .map(|(i, mode)| Some(mode.operand(*mem.get(addr + 1 + i)?)))
+        .collect::<Option<_>>()?;
+    Some(Instruction { op, operands })
+}
+
+/// One line of a listing besides labels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Item {
+    Code(usize, Instruction),
+    Data(usize, Vec<Int>),
+}
+
+/// Memory split into reachable code and data, see [`disassemble`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listing {
+    pub items: Vec<Item>,
+    /// Addresses of instructions that are jumped to.
+    pub labels: BTreeSet<usize>,
+}
+
+/// Number of values per `.data` line.
+const DATA_PER_LINE: usize = 8;
+
+/// Disassembles `mem`, following the control flow from address 0.
+pub fn disassemble(mem: &[Int]) -> Listing {
+    let mut code: HashMap<usize, Instruction> = HashMap::new();
+    let mut claimed = vec![false; mem.len()];
+    let mut todo = vec![0];
+    while let Some(addr) = todo.pop() {
+        if addr >= mem.len() || claimed[addr] {
+            continue;
+        }
+        let ins = match decode(mem, addr) {
+            Some(ins) => ins,
+            None => continue,
+        };
+        // Instructions overlapping one that is already known stay data.
+        if claimed[addr..addr + ins.size()].iter().any(|c| *c) {
+            continue;
+        }
+        claimed[addr..addr + ins.size()].fill(true);
+        todo.extend(ins.successors(addr));
+        code.insert(addr, ins);
+    }
+    let labels = code
+        .values()
+        .filter_map(Instruction::jump_target)
+        .filter(|t| code.contains_key(t))
+        .collect();
+
+    let mut items = Vec::new();
+    let mut addr = 0;
+    while addr < mem.len() {
+        if let Some(ins) = code.remove(&addr) {
+            let size = ins.size();
+            items.push(Item::Code(addr, ins));
+            addr += size;
+        } else {
+            let end = (addr..mem.len())
+                .take(DATA_PER_LINE)
+                .find(|a| claimed[*a])
+                .unwrap_or(mem.len().min(addr + DATA_PER_LINE));
+            items.push(Item::Data(addr, mem[addr..end].to_vec()));
+            addr = end;
+        }
+    }
+    Listing { items, labels }
+}
+
+impl std::fmt::Display for Listing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for item in &self.items {
+            let (addr, text) = match item {
+                Item::Code(addr, ins) => {
+                    let operands: Vec<String> = ins
+                        .operands
+                        .iter()
+                        .enumerate()
+                        .map(|(i, op)| match (op, ins.jump_target()) {
+                            (Operand::Imm(_), Some(t)) if i == 1 && self.labels.contains(&t) => {
+                                format!("#l{}", t)
+                            }
+                            _ => op.to_string(),
+                        })
+                        .collect();
+                    (*addr, format!("{} {}", ins.op, operands.join(", ")))
+                }
+                Item::Data(addr, values) => {
+                    let values: Vec<String> = values.iter().map(Int::to_string).collect();
+                    (*addr, format!(".data {}", values.join(", ")))
+                }
+            };
+            if self.labels.contains(&addr) {
+                writeln!(f, "l{}:", addr)?;
+            }
+            writeln!(f, "    {:<23} ; {}", text.trim_end(), addr)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::DOUBLER;
+
+    #[test]
+    fn lists_code_and_data() {
+        assert_eq!(
+            disassemble(&DOUBLER).to_string(),
+            "l0:
+    in [100]                ; 0
+    jf [100], #l18          ; 2
+    out [100]               ; 5
+    mul [100], #2, [100]    ; 7
+    out [100]               ; 11
+    jt #1, #l0              ; 13
+    .data 0, 0              ; 16
+l18:
+    hlt                     ; 18
+"
+        );
+    }
+
+    #[test]
+    fn decodes_operand_modes() {
+        assert_eq!(
+            decode(&[21201, -3, 7, 0], 0),
+            Some(Instruction {
+                op: Op::Add,
+                operands: vec![Operand::Rel(-3), Operand::Imm(7), Operand::Rel(0)],
+            })
+        );
+        assert_eq!(
+            decode(&[21201, -3, 7, 0], 0).unwrap().operands[0].to_string(),
+            "rb-3"
+        );
+        assert_eq!(decode(&[1105, 1], 0), None);
+        assert_eq!(decode(&[399, 0], 0), None);
+        assert_eq!(decode(&[30004, 0], 0), None);
+        // The machine ignores the third mode digit of a jump.
+        assert_eq!(decode(&[11105, 1, 0], 0).unwrap().to_string(), "jt #1, #0");
+        assert_eq!(
+            disassemble(&[11105, 1, 3, 99]).to_string(),
+            "    jt #1, #l3              ; 0
+l3:
+    hlt                     ; 3
+"
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Counts executed instructions per address and finds the loops they spend
+//! their time in, fed by [`Machine::run_with_hook`](super::Machine::run_with_hook).
+use super::disasm::decode;
+use super::{Int, Op, TraceEvent};
+use std::collections::{BTreeMap, HashMap};
+
+/// A loop found by a jump back from `end` to `start`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loop {
+    pub start: usize,
+    pub end: usize,
+    /// How often the jump back was taken.
+    pub iterations: u64,
+    /// Instructions executed at addresses from `start` to `end`.
+    pub instructions: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    counts: BTreeMap<usize, u64>,
+    back_jumps: HashMap<(usize, usize), u64>,
+    total: u64,
+}
+
+impl Profile {
+    pub fn record(&mut self, event: &TraceEvent) {
+        *self.counts.entry(event.ip).or_default() += 1;
+        if matches!(event.op, Op::Jt | Op::Jf) && event.next_ip < event.ip {
+            *self
+                .back_jumps
+                .entry((event.next_ip, event.ip))
+                .or_default() += 1;
+        }
+        self.total += 1;
+    }
+
+    /// Number of executed instructions.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+    /// How often the instruction at `addr` was executed.
+    pub fn count(&self, addr: usize) -> u64 {
+        self.counts.get(&addr).copied().unwrap_or(0)
+    }
+
+    /// The `n` most executed addresses with their counts.
+    pub fn hot_addresses(&self, n: usize) -> Vec<(usize, u64)> {
+        let mut res: Vec<_> = self.counts.iter().map(|(a, c)| (*a, *c)).collect();
+        res.sort_by_key(|(a, c)| (std::cmp::Reverse(*c), *a));
+        res.truncate(n);
+        res
+    }
+
+    /// The `n` loops with the most instructions executed inside them.
+    pub fn hot_loops(&self, n: usize) -> Vec<Loop> {
+        let mut res: Vec<Loop> = self
+            .back_jumps
+            .iter()
+            .map(|(&(start, end), &iterations)| Loop {
+                start,
+                end,
+                iterations,
+                instructions: self.counts.range(start..=end).map(|(_, c)| c).sum(),
+            })
+            .collect();
+        res.sort_by_key(|l| (std::cmp::Reverse(l.instructions), l.start, l.end));
+        res.truncate(n);
+        res
+    }
+
+    /// A report of the total and the `n` hottest addresses and loops, with the
+    /// instructions disassembled from `mem`.
+    pub fn report(&self, mem: &[Int], n: usize) -> String {
+        let percent = |c: u64| 100.0 * c as f64 / self.total.max(1) as f64;
+        let mut res = format!("{} instructions executed\n", self.total);
+        res.push_str("\nhot addresses:\n");
+        for (addr, count) in self.hot_addresses(n) {
+            let ins = decode(mem, addr)
+                .map(|ins| ins.to_string())
+                .unwrap_or_else(|| "?".to_string());
+            res.push_str(&format!(
+                "{:>12} {:>5.1}% {:>6}: {}\n",
+                count,
+                percent(count),
+                addr,
+                ins
+            ));
+        }
+        res.push_str("\nhot loops:\n");
+        for l in self.hot_loops(n) {
+            res.push_str(&format!(
+                "{:>12} {:>5.1}% {:>6}..{} ({} iterations)\n",
+                l.instructions,
+                percent(l.instructions),
+                l.start,
+                l.end,
+                l.iterations
+            ));
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::asm::assemble;
+    use crate::intcode::Machine;
+
+    #[test]
+    fn finds_hot_loops() {
+        // Counts [i] down from 3, adding [j] up to 2 each time.
+        let mem = assemble(
+            "outer:  add #0, #0, [j]
+            inner:  add [j], #1, [j]
+                    lt [j], #2, [t]
+                    jt [t], #inner
+                    add [i], #-1, [i]
+                    jt [i], #outer
+                    hlt
+            i:      .data 3
+            j:      .data 0
+            t:      .data 0",
+        )
+        .unwrap();
+        let mut profile = Profile::default();
+        let (halted, _) = Machine::from_vec(mem.clone())
+            .run_with_hook(None, |e| profile.record(e))
+            .unwrap();
+        assert!(halted);
+        assert_eq!(profile.total(), 3 * (1 + 2 * 3 + 2) + 1);
+        assert_eq!(profile.count(4), 6);
+        assert_eq!(profile.hot_addresses(1), [(4, 6)]);
+        assert_eq!(
+            profile.hot_loops(10),
+            [
+                Loop {
+                    start: 0,
+                    end: 19,
+                    iterations: 2,
+                    instructions: 27,
+                },
+                Loop {
+                    start: 4,
+                    end: 12,
+                    iterations: 3,
+                    instructions: 18,
+                },
+            ]
+        );
+        let report = profile.report(&mem, 1);
+        assert_eq!(
+            report.lines().collect::<Vec<_>>(),
+            [
+                "28 instructions executed",
+                "",
+                "hot addresses:",
+                "           6  21.4%      4: add [24], #1, [24]",
+                "",
+                "hot loops:",
+                "          27  96.4%      0..19 (2 iterations)",
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_halt_and_straight_code() {
+        let mut profile = Profile::default();
+        Machine::from_vec(vec![1101, 1, 2, 7, 99, 0, 0, 0])
+            .run_with_hook(None, |e| profile.record(e))
+            .unwrap();
+        assert_eq!(profile.total(), 2);
+        assert_eq!(profile.hot_loops(10), []);
+    }
+}
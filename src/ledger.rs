@@ -0,0 +1,236 @@
+use crate::input::{generate_path, Error, Verdict};
+use std::io::Write;
+
+/// One answer a day/part produced, with the server's verdict if it was submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Option<Verdict>,
+}
+
+/// Persisted history of answers and verdicts for a single day, stored as
+/// `input/<year>/<day>.ledger` next to the cached input.
+#[derive(Debug)]
+pub struct Ledger {
+    path: std::path::PathBuf,
+    entries: Vec<Entry>,
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+fn unescape(s: &str) -> String {
+    let mut res = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => res.push('\t'),
+                Some('n') => res.push('\n'),
+                Some(c) => res.push(c),
+                None => res.push('\\'),
+            }
+        } else {
+            res.push(c);
+        }
+    }
+    res
+}
+
+fn verdict_to_str(verdict: &Option<Verdict>) -> &'static str {
+    match verdict {
+        None => "produced",
+        Some(Verdict::Correct) => "correct",
+        Some(Verdict::TooHigh) => "too_high",
+        Some(Verdict::TooLow) => "too_low",
+        Some(Verdict::Wrong) => "wrong",
+        Some(Verdict::RateLimited(_)) => "rate_limited",
+    }
+}
+fn verdict_from_str(s: &str) -> Option<Option<Verdict>> {
+    Some(match s {
+        "produced" => None,
+        "correct" => Some(Verdict::Correct),
+        "too_high" => Some(Verdict::TooHigh),
+        "too_low" => Some(Verdict::TooLow),
+        "wrong" => Some(Verdict::Wrong),
+        _ => return None,
+    })
+}
+
+impl Ledger {
+    /// Opens the ledger of the given day, which is empty if nothing was recorded yet.
+    pub fn open(year: u32, day: u32) -> Result<Self, Error> {
+        let mut path = generate_path(year, day);
+        path.set_extension("ledger");
+        Self::load(path)
+    }
+    pub fn load(path: std::path::PathBuf) -> Result<Self, Error> {
+        let mut entries = Vec::new();
+        if path.is_file() {
+            for l in ex::fs::read_to_string(&path)?.lines() {
+                let mut fields = l.splitn(3, '\t');
+                let part = fields.next().and_then(|p| p.parse().ok());
+                let verdict = fields.next().and_then(verdict_from_str);
+                if let (Some(part), Some(verdict), Some(answer)) = (part, verdict, fields.next()) {
+                    entries.push(Entry {
+                        part,
+                        answer: unescape(answer),
+                        verdict,
+                    });
+                }
+            }
+        }
+        Ok(Self { path, entries })
+    }
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Appends an entry and writes it to disk. Rate limit replies say nothing
+    /// about the answer and are not recorded.
+    pub fn record(
+        &mut self,
+        part: u32,
+        answer: &str,
+        verdict: Option<Verdict>,
+    ) -> Result<(), Error> {
+        if let Some(Verdict::RateLimited(_)) = verdict {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent() {
+            ex::fs::create_dir_all(dir)?;
+        }
+        let mut file = ex::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let line = format!(
+            "{}\t{}\t{}\n",
+            part,
+            verdict_to_str(&verdict),
+            escape(answer)
+        );
+        file.write_all(line.as_bytes())
+            .map_err(|e| ex::io::Error::Filesystem(self.path.clone(), "writing", e))?;
+        self.entries.push(Entry {
+            part,
+            answer: answer.to_string(),
+            verdict,
+        });
+        Ok(())
+    }
+
+    /// Records an answer a solution produced, unless the part already has an
+    /// entry with it.
+    pub fn record_produced(&mut self, part: u32, answer: &str) -> Result<(), Error> {
+        if self
+            .entries
+            .iter()
+            .any(|e| e.part == part && e.answer == answer)
+        {
+            return Ok(());
+        }
+        self.record(part, answer, None)
+    }
+
+    fn verdicts(&self, part: u32) -> impl Iterator<Item = (&str, &Verdict)> {
+        self.entries
+            .iter()
+            .filter(move |e| e.part == part)
+            .filter_map(|e| e.verdict.as_ref().map(|v| (e.answer.as_str(), v)))
+    }
+
+    /// Checks whether submitting `answer` could tell us anything new.
+    ///
+    /// Returns the known verdict if the answer was already accepted, `None` if it
+    /// should be sent, and an error if it is known or implied to be wrong.
+    pub fn check(&self, part: u32, answer: &str) -> Result<Option<Verdict>, Error> {
+        for (a, v) in self.verdicts(part) {
+            match v {
+                Verdict::Correct if a == answer => return Ok(Some(Verdict::Correct)),
+                Verdict::Correct => return Err(Error::AlreadySolved(a.to_string())),
+                v if a == answer => return Err(Error::KnownWrong(v.clone())),
+                _ => {}
+            }
+        }
+        if let Ok(value) = answer.trim().parse::<i64>() {
+            let parse = |a: &str| a.trim().parse::<i64>().ok();
+            let high = self
+                .verdicts(part)
+                .filter(|(_, v)| **v == Verdict::TooHigh)
+                .filter_map(|(a, _)| parse(a))
+                .min();
+            let low = self
+                .verdicts(part)
+                .filter(|(_, v)| **v == Verdict::TooLow)
+                .filter_map(|(a, _)| parse(a))
+                .max();
+            if high.map(|h| value >= h).unwrap_or(false) || low.map(|l| value <= l).unwrap_or(false)
+            {
+                return Err(Error::OutOfBounds { value, low, high });
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_ledger(name: &str) -> Ledger {
+        let path = std::env::temp_dir().join(format!("aoc2021-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        Ledger::load(path).unwrap()
+    }
+
+    #[test]
+    fn rejects_known_and_bounded_answers() {
+        let mut ledger = temp_ledger("bounds");
+        ledger.record(1, "100", Some(Verdict::TooHigh)).unwrap();
+        ledger.record(1, "10", Some(Verdict::TooLow)).unwrap();
+        ledger.record(1, "50", Some(Verdict::Wrong)).unwrap();
+        assert!(matches!(
+            ledger.check(1, "50"),
+            Err(Error::KnownWrong(Verdict::Wrong))
+        ));
+        assert!(matches!(
+            ledger.check(1, "120"),
+            Err(Error::OutOfBounds {
+                high: Some(100),
+                ..
+            })
+        ));
+        assert!(matches!(
+            ledger.check(1, "5"),
+            Err(Error::OutOfBounds { low: Some(10), .. })
+        ));
+        assert!(matches!(ledger.check(1, "42"), Ok(None)));
+        assert!(matches!(ledger.check(2, "120"), Ok(None)));
+
+        ledger.record(1, "42", Some(Verdict::Correct)).unwrap();
+        assert!(matches!(ledger.check(1, "42"), Ok(Some(Verdict::Correct))));
+        assert!(matches!(
+            ledger.check(1, "43"),
+            Err(Error::AlreadySolved(_))
+        ));
+        std::fs::remove_file(&ledger.path).unwrap();
+    }
+
+    #[test]
+    fn persists_entries() {
+        let mut ledger = temp_ledger("persist");
+        ledger.record(2, "a\tb\nc", Some(Verdict::TooLow)).unwrap();
+        ledger.record(2, "7", None).unwrap();
+        ledger.record_produced(2, "7").unwrap();
+        ledger.record_produced(1, "7").unwrap();
+        assert_eq!(ledger.entries().len(), 3);
+        let loaded = Ledger::load(ledger.path.clone()).unwrap();
+        assert_eq!(loaded.entries(), ledger.entries());
+        std::fs::remove_file(&ledger.path).unwrap();
+    }
+}
@@ -1,3 +1,39 @@
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod grid;
 mod input;
 pub mod intcode;
-pub use input::{get_input, Error};
+pub mod ledger;
+pub mod puzzle;
+pub mod search;
+pub mod solution;
+pub use input::{
+    find_session, get_input, get_input_for, submit_answer, Error, Input, InputSource, Verdict,
+    DEFAULT_YEAR,
+};
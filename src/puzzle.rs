@@ -0,0 +1,415 @@
+use crate::client::Client;
+use crate::input::{generate_path, http_get, read_cached, store_cached, Error};
+
+/// A puzzle description with one article per unlocked part.
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+    articles: Vec<String>,
+}
+
+/// Example input from a puzzle description with the answers given for it, one
+/// per unlocked part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample {
+    pub input: String,
+    pub answers: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Text,
+    Markdown,
+}
+
+enum Token<'a> {
+    Open(&'a str, &'a str),
+    Close(&'a str),
+    Text(&'a str),
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut res = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        if start > 0 {
+            res.push(Token::Text(&rest[..start]));
+        }
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let tag = rest[start + 1..end].trim_end_matches('/').trim();
+        if let Some(name) = tag.strip_prefix('/') {
+            res.push(Token::Close(name.trim()));
+        } else if !tag.starts_with('!') {
+            let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            res.push(Token::Open(name, attrs));
+        }
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        res.push(Token::Text(rest));
+    }
+    res
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn get_attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let start = attrs.find(&format!("{}=\"", name))? + name.len() + 2;
+    let len = attrs[start..].find('"')?;
+    Some(&attrs[start..start + len])
+}
+
+/// Ends the current block so that the next one starts after an empty line.
+fn block_break(out: &mut String) {
+    while out.ends_with(' ') {
+        out.pop();
+    }
+    if !out.is_empty() {
+        while !out.ends_with("\n\n") {
+            out.push('\n');
+        }
+    }
+}
+
+fn render(html: &str, style: Style) -> String {
+    let md = style == Style::Markdown;
+    let mut out = String::new();
+    let mut pre: Option<String> = None;
+    let mut in_code = false;
+    let mut links = Vec::new();
+    for token in tokenize(html) {
+        match token {
+            Token::Text(t) => {
+                let t = decode_entities(t);
+                if let Some(p) = pre.as_mut() {
+                    p.push_str(&t);
+                } else {
+                    for (i, word) in t.split(char::is_whitespace).enumerate() {
+                        let at_line_start = out.is_empty() || out.ends_with('\n');
+                        if i > 0 && !at_line_start && !out.ends_with(' ') {
+                            out.push(' ');
+                        }
+                        out.push_str(word);
+                    }
+                }
+            }
+            Token::Open("h2", _) => {
+                block_break(&mut out);
+                if md {
+                    out.push_str("## ");
+                }
+            }
+            Token::Open("p", _) | Token::Open("ul", _) => block_break(&mut out),
+            Token::Close("h2") | Token::Close("p") | Token::Close("ul") => block_break(&mut out),
+            Token::Open("li", _) => {
+                while out.ends_with(' ') {
+                    out.pop();
+                }
+                if !out.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("- ");
+            }
+            Token::Open("pre", _) => {
+                block_break(&mut out);
+                pre = Some(String::new());
+            }
+            Token::Close("pre") => {
+                let block = pre.take().unwrap_or_default();
+                let block = block.trim_end_matches('\n');
+                if md {
+                    out.push_str("```\n");
+                    out.push_str(block);
+                    out.push_str("\n```");
+                } else {
+                    for l in block.lines() {
+                        out.push_str("    ");
+                        out.push_str(l);
+                        out.push('\n');
+                    }
+                }
+                block_break(&mut out);
+            }
+            Token::Open("code", _) if pre.is_none() => {
+                in_code = true;
+                if md {
+                    out.push('`');
+                }
+            }
+            Token::Close("code") if pre.is_none() => {
+                in_code = false;
+                if md {
+                    out.push('`');
+                }
+            }
+            Token::Open("em", _) | Token::Close("em") if pre.is_some() => {}
+            Token::Open("em", _) => {
+                if !md {
+                    out.push_str("\x1b[1m");
+                } else if !in_code {
+                    out.push_str("**");
+                }
+            }
+            Token::Close("em") => {
+                if !md {
+                    out.push_str("\x1b[0m");
+                } else if !in_code {
+                    out.push_str("**");
+                }
+            }
+            Token::Open("a", attrs) => {
+                links.push(get_attr(attrs, "href").unwrap_or("").to_string());
+                if md {
+                    out.push('[');
+                }
+            }
+            Token::Close("a") => {
+                let href = links.pop().unwrap_or_default();
+                if md {
+                    out.push_str(&format!("]({})", href));
+                }
+            }
+            _ => {}
+        }
+    }
+    format!("{}\n", out.trim_end())
+}
+
+/// Text content of an HTML fragment without any tags.
+fn strip_tags(html: &str) -> String {
+    tokenize(html)
+        .into_iter()
+        .filter_map(|t| match t {
+            Token::Text(t) => Some(decode_entities(t)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn extract_articles(html: &str) -> Vec<String> {
+    let mut res = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<article") {
+        let body_start = match rest[start..].find('>') {
+            Some(end) => start + end + 1,
+            None => break,
+        };
+        let body_end = match rest[body_start..].find("</article>") {
+            Some(end) => body_start + end,
+            None => break,
+        };
+        res.push(rest[body_start..body_end].to_string());
+        rest = &rest[body_end..];
+    }
+    res
+}
+
+fn html_path(year: u32, day: u32) -> std::path::PathBuf {
+    let mut path = generate_path(year, day);
+    path.set_extension("html");
+    path
+}
+
+impl Puzzle {
+    /// Builds a puzzle from the `<article>` elements in `html`.
+    pub fn from_html(year: u32, day: u32, html: &str) -> Self {
+        Self {
+            year,
+            day,
+            articles: extract_articles(html),
+        }
+    }
+
+    /// Number of parts whose description is available.
+    pub fn parts(&self) -> usize {
+        self.articles.len()
+    }
+
+    pub fn to_text(&self) -> String {
+        self.render(Style::Text)
+    }
+
+    pub fn to_markdown(&self) -> String {
+        self.render(Style::Markdown)
+    }
+
+    fn render(&self, style: Style) -> String {
+        self.articles
+            .iter()
+            .map(|a| render(a, style))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// All `<pre><code>` blocks of the description.
+    pub fn examples(&self) -> Vec<String> {
+        let mut res = Vec::new();
+        for a in &self.articles {
+            let mut rest = a.as_str();
+            while let Some(start) = rest.find("<pre><code>") {
+                let body = &rest[start + "<pre><code>".len()..];
+                let end = body.find("</code></pre>").unwrap_or(body.len());
+                res.push(strip_tags(&body[..end]));
+                rest = &body[end..];
+            }
+        }
+        res
+    }
+
+    /// The emphasized code of each part. The last one of a part is usually the
+    /// answer for the example.
+    pub fn emphasized(&self) -> Vec<Vec<String>> {
+        self.articles
+            .iter()
+            .map(|a| {
+                let mut res = Vec::new();
+                let mut rest = a.as_str();
+                while let Some(start) = rest.find("<code><em>") {
+                    let body = &rest[start + "<code><em>".len()..];
+                    let end = body.find("</em></code>").unwrap_or(body.len());
+                    res.push(strip_tags(&body[..end]));
+                    rest = &body[end..];
+                }
+                res
+            })
+            .collect()
+    }
+
+    /// The first example with the expected answer of every unlocked part.
+    pub fn sample(&self) -> Option<Sample> {
+        let input = self.examples().into_iter().next()?;
+        let answers = self
+            .emphasized()
+            .into_iter()
+            .filter_map(|e| e.last().cloned())
+            .collect();
+        Some(Sample { input, answers })
+    }
+}
+
+/// The articles of a fetched puzzle page, ready to be cached. A page without
+/// any, like a login or error page, is rejected.
+fn page_articles(page: &[u8]) -> Result<String, Error> {
+    let page = String::from_utf8_lossy(page);
+    let articles = extract_articles(&page);
+    if articles.is_empty() {
+        return Err(Error::UnexpectedResponse(page.into_owned()));
+    }
+    Ok(articles
+        .iter()
+        .map(|a| format!("<article>{}</article>\n", a))
+        .collect())
+}
+
+/// Returns the description of the given day, fetching it if it is not cached yet.
+///
+/// The articles are cached as `input/<year>/<day>.html`. A cached copy without
+/// part 2 is fetched again once it is older than the minimum interval between
+/// requests, so part 2 appears however part 1 was solved. If that fails, the
+/// cached copy is used.
+pub fn get_puzzle(year: u32, day: u32) -> Result<Puzzle, Error> {
+    let path = html_path(year, day);
+    let cached = read_cached(&path).map(|d| String::from_utf8_lossy(&d).into_owned());
+    let stale = std::fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_none_or(|age| age >= Client::from_env().min_interval());
+    let fetch = || -> Result<String, Error> {
+        let html = page_articles(&http_get(year, day, "")?)?;
+        store_cached(&path, html.as_bytes())?;
+        Ok(html)
+    };
+    let html = match cached {
+        Some(html) if !stale || extract_articles(&html).len() >= 2 => html,
+        Some(html) => fetch().unwrap_or(html),
+        None => fetch()?,
+    };
+    Ok(Puzzle::from_html(year, day, &html))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Sonar Sweep ---</h2><p>For example, suppose you had the following <a href="/2021/about">report</a>:</p>
+<pre><code>199
+200
+&lt;208&gt;
+</code></pre>
+<p>In this example, there are <code><em>7</em></code> measurements that are larger.</p>
+</article>
+<p>Answer: <input type="text" name="answer"/></p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>There are <code><em>5</em></code> sums that are <em>larger</em>.</p>
+<ul>
+<li>one</li>
+<li>two</li>
+</ul>
+</article>
+</main>"#;
+
+    #[test]
+    fn extracts_sample() {
+        let puzzle = Puzzle::from_html(2021, 1, PAGE);
+        assert_eq!(puzzle.parts(), 2);
+        assert_eq!(
+            puzzle.sample(),
+            Some(Sample {
+                input: "199\n200\n<208>\n".to_string(),
+                answers: vec!["7".to_string(), "5".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_pages_without_articles() {
+        let html = page_articles(PAGE.as_bytes()).unwrap();
+        assert_eq!(Puzzle::from_html(2021, 1, &html).parts(), 2);
+        assert!(html.starts_with("<article><h2>"));
+        let login = b"<main><p>Please log in.</p></main>";
+        assert!(matches!(
+            page_articles(login),
+            Err(Error::UnexpectedResponse(_))
+        ));
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let puzzle = Puzzle::from_html(2021, 1, PAGE);
+        assert_eq!(
+            puzzle.to_markdown(),
+            "## --- Day 1: Sonar Sweep ---
+
+For example, suppose you had the following [report](/2021/about):
+
+```
+199
+200
+<208>
+```
+
+In this example, there are `7` measurements that are larger.
+
+## --- Part Two ---
+
+There are `5` sums that are **larger**.
+
+- one
+- two
+"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The cheapest path to a goal found by [`dijkstra`] or [`a_star`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<S, C> {
+    pub cost: C,
+    /// All states from the start to the goal, both included.
+    pub path: Vec<S>,
+    /// Number of states taken from the queue before the goal was reached.
+    pub expanded: usize,
+}
+
+/// Cost of a step or path. `Default` must be zero.
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+impl<C: Copy + Ord + Default + Add<Output = C>> Cost for C {}
+
+/// Finds the cheapest path from `start` to a state satisfying `is_goal`.
+/// `successors` yields the neighbors of a state with the cost to step there.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    a_star(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but explores states in the order of their cost plus
+/// `heuristic`. The heuristic must never overestimate the remaining cost.
+pub fn a_star<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // States are numbered in the order they are discovered, `nodes` holds the
+    // best known cost and predecessor of each.
+    let mut states = vec![start.clone()];
+    let mut index = HashMap::from([(start.clone(), 0)]);
+    let mut nodes: Vec<(C, Option<usize>)> = vec![(C::default(), None)];
+    // Among equal estimates the deeper state comes first.
+    let mut queue = BinaryHeap::from([(Reverse(heuristic(&start)), C::default(), 0)]);
+    let mut expanded = 0;
+    while let Some((_, cost, i)) = queue.pop() {
+        if cost > nodes[i].0 {
+            continue;
+        }
+        expanded += 1;
+        if is_goal(&states[i]) {
+            let mut path = vec![states[i].clone()];
+            let mut prev = nodes[i].1;
+            while let Some(p) = prev {
+                path.push(states[p].clone());
+                prev = nodes[p].1;
+            }
+            path.reverse();
+            return Some(Found {
+                cost,
+                path,
+                expanded,
+            });
+        }
+        for (next, step) in successors(&states[i]) {
+            let next_cost = cost + step;
+            let j = match index.get(&next) {
+                Some(&j) if nodes[j].0 <= next_cost => continue,
+                Some(&j) => {
+                    nodes[j] = (next_cost, Some(i));
+                    j
+                }
+                None => {
+                    let j = states.len();
+                    index.insert(next.clone(), j);
+                    nodes.push((next_cost, Some(i)));
+                    states.push(next);
+                    j
+                }
+            };
+            let estimate = next_cost + heuristic(&states[j]);
+            queue.push((Reverse(estimate), next_cost, j));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Steps of one or two along a line of length 10, where a step of two
+    /// costs three.
+    fn line(pos: &u32) -> Vec<(u32, u32)> {
+        [(pos + 1, 1), (pos + 2, 3)]
+            .into_iter()
+            .filter(|(p, _)| *p < 10)
+            .collect()
+    }
+
+    #[test]
+    fn finds_cheapest_path() {
+        let found = dijkstra(0, line, |p| *p == 4).unwrap();
+        assert_eq!(found.cost, 4);
+        assert_eq!(found.path, [0, 1, 2, 3, 4]);
+        assert_eq!(dijkstra(0, line, |p| *p == 10), None);
+    }
+
+    #[test]
+    fn heuristic_reduces_expansion() {
+        let successors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|(x, y)| (0..20).contains(x) && (0..20).contains(y))
+                .map(|p| (p, 1))
+        };
+        let goal = |p: &(i32, i32)| *p == (19, 19);
+        let plain = dijkstra((0, 0), successors, goal).unwrap();
+        let directed = a_star((0, 0), successors, |(x, y)| 38 - x - y, goal).unwrap();
+        assert_eq!(plain.cost, 38);
+        assert_eq!(directed.cost, 38);
+        assert_eq!(directed.path.len(), 39);
+        assert!(directed.expanded < plain.expanded);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{Error, InputSource};
+use std::time::{Duration, Instant};
+
+/// A puzzle answer, either a number or text like the letters of day 13.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Num(i64),
+    Text(String),
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Num(v) => write!(f, "{}", v),
+            Self::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Self {
+                    match i64::try_from(v) {
+                        Ok(v) => Self::Num(v),
+                        Err(_) => Self::Text(v.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+answer_from_int!(i32, u32, i64, u64, usize);
+
+impl From<String> for Answer {
+    fn from(v: String) -> Self {
+        Self::Text(v)
+    }
+}
+impl From<&str> for Answer {
+    fn from(v: &str) -> Self {
+        Self::Text(v.to_string())
+    }
+}
+
+impl Answer {
+    /// The answer as a JSON number or string.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Num(v) => (*v).into(),
+            Self::Text(v) => v.as_str().into(),
+        }
+    }
+}
+
+/// A day of the calendar. The input is parsed once into a `Model` that both
+/// parts work on.
+pub trait Solution {
+    const DAY: u32;
+    type Model;
+
+    fn parse(input: &str) -> Result<Self::Model, Error>;
+    fn part1(model: &Self::Model) -> Result<Answer, Error>;
+    fn part2(model: &Self::Model) -> Result<Answer, Error>;
+}
+
+/// Answers of one day together with the time each step took. Each part has
+/// its own result, so a failing part 2 keeps the answer of part 1.
+#[derive(Debug)]
+pub struct DayResult {
+    pub day: u32,
+    pub parse: Duration,
+    pub parts: [(Result<Answer, Error>, Duration); 2],
+}
+
+impl DayResult {
+    /// Both answers with their times, or the error of the first failed part.
+    pub fn answers(self) -> Result<[(Answer, Duration); 2], Error> {
+        let [(part1, time1), (part2, time2)] = self.parts;
+        Ok([(part1?, time1), (part2?, time2)])
+    }
+}
+
+/// The result of a day as one JSON object, e.g.
+/// `{"day":1,"parse_ns":900,"parts":[{"part":1,"answer":7,"elapsed_ns":300},...],"error":null}`.
+/// A failed part has an `error` instead of its `answer`. If parsing fails,
+/// `parts` is empty and the top level `error` holds the message.
+pub fn result_to_json(day: u32, result: &Result<DayResult, Error>) -> serde_json::Value {
+    match result {
+        Ok(r) => serde_json::json!({
+            "day": day,
+            "parse_ns": r.parse.as_nanos() as u64,
+            "parts": r.parts.iter().enumerate().map(|(i, (answer, elapsed))| {
+                let mut part = serde_json::json!({
+                    "part": i + 1,
+                    "elapsed_ns": elapsed.as_nanos() as u64,
+                });
+                match answer {
+                    Ok(answer) => part["answer"] = answer.to_json(),
+                    Err(e) => part["error"] = format!("{:?}", e).into(),
+                }
+                part
+            }).collect::<Vec<_>>(),
+            "error": null,
+        }),
+        Err(e) => serde_json::json!({
+            "day": day,
+            "parts": [],
+            "error": format!("{:?}", e),
+        }),
+    }
+}
+
+/// Parses `input` and solves both parts of `S`. Only a failed parse is an
+/// error, parts that fail are reported in the result.
+pub fn run<S: Solution>(input: &str) -> Result<DayResult, Error> {
+    let start = Instant::now();
+    let model = S::parse(input)?;
+    let parse = start.elapsed();
+    let start = Instant::now();
+    let part1 = S::part1(&model);
+    let part1_time = start.elapsed();
+    let start = Instant::now();
+    let part2 = S::part2(&model);
+    let part2_time = start.elapsed();
+    Ok(DayResult {
+        day: S::DAY,
+        parse,
+        parts: [(part1, part1_time), (part2, part2_time)],
+    })
+}
+
+/// All days that have a solution.
+pub const DAYS: std::ops::RangeInclusive<u32> = 1..=25;
+
+/// Runs the solution of `day` on `input`.
+pub fn run_day(day: u32, input: &str) -> Result<DayResult, Error> {
+    use crate::*;
+    match day {
+        1 => run::<day1::Day1>(input),
+        2 => run::<day2::Day2>(input),
+        3 => run::<day3::Day3>(input),
+        4 => run::<day4::Day4>(input),
+        5 => run::<day5::Day5>(input),
+        6 => run::<day6::Day6>(input),
+        7 => run::<day7::Day7>(input),
+        8 => run::<day8::Day8>(input),
+        9 => run::<day9::Day9>(input),
+        10 => run::<day10::Day10>(input),
+        11 => run::<day11::Day11>(input),
+        12 => run::<day12::Day12>(input),
+        13 => run::<day13::Day13>(input),
+        14 => run::<day14::Day14>(input),
+        15 => run::<day15::Day15>(input),
+        16 => run::<day16::Day16>(input),
+        17 => run::<day17::Day17>(input),
+        18 => run::<day18::Day18>(input),
+        19 => run::<day19::Day19>(input),
+        20 => run::<day20::Day20>(input),
+        21 => run::<day21::Day21>(input),
+        22 => run::<day22::Day22>(input),
+        23 => run::<day23::Day23>(input),
+        24 => run::<day24::Day24>(input),
+        25 => run::<day25::Day25>(input),
+        _ => Err(Error::NoSolution(day)),
+    }
+}
+
+/// Reads the input of `day` from the source selected by [`InputSource::from_env`].
+pub fn read_input(day: u32) -> Result<String, Error> {
+    InputSource::from_env().read_to_string(crate::input::DEFAULT_YEAR, day)
+}
+
+/// Solves `S` on its input and prints both answers, used by the examples.
+pub fn print_day<S: Solution>() -> Result<(), Error> {
+    print_day_with_input::<S>(&read_input(S::DAY)?)
+}
+
+/// Like [`print_day`] for examples that already read the input, which can
+/// only be read once from stdin.
+pub fn print_day_with_input<S: Solution>(input: &str) -> Result<(), Error> {
+    let result = run::<S>(input)?;
+    for (i, (answer, _)) in result.parts.into_iter().enumerate() {
+        let answer = answer?;
+        if answer.to_string().contains('\n') {
+            println!("Answer {}:\n{}", i + 1, answer);
+        } else {
+            println!("Answer {}: {}", i + 1, answer);
+        }
+    }
+    Ok(())
+}
+
+/// Asserts that `S` gives the expected answers for `input`.
+#[cfg(test)]
+pub(crate) fn assert_answers<S: Solution>(
+    input: &str,
+    part1: impl Into<Answer>,
+    part2: impl Into<Answer>,
+) {
+    let [(answer1, _), (answer2, _)] = run::<S>(input).unwrap().answers().unwrap();
+    assert_eq!(answer1, part1.into(), "part 1 of day {}", S::DAY);
+    assert_eq!(answer2, part2.into(), "part 2 of day {}", S::DAY);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_results() {
+        let result = DayResult {
+            day: 13,
+            parse: Duration::from_nanos(5),
+            parts: [
+                (Ok(Answer::Num(17)), Duration::from_nanos(7)),
+                (
+                    Ok(Answer::Text("#\n#".to_string())),
+                    Duration::from_nanos(9),
+                ),
+            ],
+        };
+        assert_eq!(
+            result_to_json(13, &Ok(result)).to_string(),
+            r##"{"day":13,"error":null,"parse_ns":5,"parts":[{"answer":17,"elapsed_ns":7,"part":1},{"answer":"#\n#","elapsed_ns":9,"part":2}]}"##
+        );
+        let result = DayResult {
+            day: 7,
+            parse: Duration::from_nanos(5),
+            parts: [
+                (Ok(Answer::Num(17)), Duration::from_nanos(7)),
+                (Err(Error::solve(7, "no fuel")), Duration::from_nanos(9)),
+            ],
+        };
+        assert_eq!(
+            result_to_json(7, &Ok(result)).to_string(),
+            r#"{"day":7,"error":null,"parse_ns":5,"parts":[{"answer":17,"elapsed_ns":7,"part":1},{"elapsed_ns":9,"error":"day 7 failed: no fuel","part":2}]}"#
+        );
+        assert_eq!(
+            result_to_json(26, &Err(Error::NoSolution(26))).to_string(),
+            r#"{"day":26,"error":"there is no solution for day 26","parts":[]}"#
+        );
+    }
+}